@@ -1,7 +1,8 @@
 use sp_core::{Pair, Public, sr25519};
 use node_template_runtime::{
 	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig,
-	SudoConfig, SystemConfig, WASM_BINARY, Signature
+	SudoConfig, SystemConfig, MolochV1Config, WASM_BINARY, Signature,
+	pallet_moloch_v1::{GuildGenesis, GuildParameters},
 };
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_finality_grandpa::AuthorityId as GrandpaId;
@@ -68,6 +69,11 @@ pub fn development_config() -> Result<ChainSpec, String> {
 				get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 				get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
 			],
+			// Founders of the genesis guild, with their shares and loot
+			vec![
+				(get_account_id_from_seed::<sr25519::Public>("Alice"), 100, 0),
+				(get_account_id_from_seed::<sr25519::Public>("Bob"), 50, 50),
+			],
 			true,
 		),
 		// Bootnodes
@@ -116,6 +122,12 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 				get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 				get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 			],
+			// Founders of the genesis guild, with their shares and loot
+			vec![
+				(get_account_id_from_seed::<sr25519::Public>("Alice"), 100, 0),
+				(get_account_id_from_seed::<sr25519::Public>("Bob"), 100, 0),
+				(get_account_id_from_seed::<sr25519::Public>("Charlie"), 50, 50),
+			],
 			true,
 		),
 		// Bootnodes
@@ -137,6 +149,7 @@ fn testnet_genesis(
 	initial_authorities: Vec<(AuraId, GrandpaId)>,
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
+	moloch_founders: Vec<(AccountId, u128, u128)>,
 	_enable_println: bool,
 ) -> GenesisConfig {
 	GenesisConfig {
@@ -159,5 +172,22 @@ fn testnet_genesis(
 			// Assign network admin rights.
 			key: root_key,
 		}),
+		pallet_moloch_v1: Some(MolochV1Config {
+			guilds: vec![GuildGenesis {
+				// periods of one minute
+				period_duration: 60,
+				parameters: GuildParameters {
+					voting_period_length: 5,
+					grace_period_length: 3,
+					abort_window: 2,
+					dilution_bound: 3,
+					proposal_deposit: 1 << 40,
+					processing_reward: 1 << 38,
+					thresholds: Vec::new(),
+				},
+				founders: moloch_founders,
+				funding: 1 << 50,
+			}],
+		}),
 	}
 }
@@ -35,12 +35,14 @@ pub fn create_full<C, P>(
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: pallet_quadratic_funding_rpc::QuadraticFundingRuntimeApi<Block, AccountId, Hash>,
+	C::Api: pallet_moloch_v1_rpc::MolochRuntimeApi<Block, AccountId, Balance, Hash>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
 {
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
 	use pallet_quadratic_funding_rpc::{QuadraticFunding, QuadraticFundingApi};
+	use pallet_moloch_v1_rpc::{Moloch, MolochApi};
 
 	let mut io = jsonrpc_core::IoHandler::default();
 	let FullDeps {
@@ -64,5 +66,9 @@ pub fn create_full<C, P>(
 		QuadraticFundingApi::to_delegate(QuadraticFunding::new(client.clone()))
 	);
 
+	io.extend_with(
+		MolochApi::to_delegate(Moloch::<_, (Block, Balance)>::new(client.clone()))
+	);
+
 	io
 }
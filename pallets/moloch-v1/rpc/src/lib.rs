@@ -0,0 +1,189 @@
+use std::sync::Arc;
+use std::convert::TryInto;
+use codec::Codec;
+use sp_blockchain::HeaderBackend;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_runtime::{generic::BlockId, traits::{Block as BlockT, MaybeDisplay}};
+use sp_api::ProvideRuntimeApi;
+use sp_rpc::number::NumberOrHex;
+pub use pallet_moloch_v1_runtime_api::{DaoId, ProposalStatus, ProposalContent, MolochApi as MolochRuntimeApi};
+pub use self::gen_client::Client as MolochClient;
+
+
+// serde_json can not use u128 https://github.com/paritytech/substrate/issues/4641, so u128 goes as NumberOrHex
+#[rpc]
+pub trait MolochApi<AccountId, Hash> {
+	#[rpc(name = "moloch_currentPeriod")]
+	fn current_period(
+		&self,
+		dao_id: DaoId,
+	) -> Result<Option<NumberOrHex>>;
+
+	/// (shares, loot, highest_index_yes_vote, delegate_key)
+	#[rpc(name = "moloch_member")]
+	fn member_info(
+		&self,
+		dao_id: DaoId,
+		who: AccountId,
+	) -> Result<Option<(NumberOrHex, NumberOrHex, Option<NumberOrHex>, AccountId)>>;
+
+	#[rpc(name = "moloch_proposalStatus")]
+	fn proposal_status(
+		&self,
+		dao_id: DaoId,
+		proposal_index: NumberOrHex,
+	) -> Result<Option<ProposalStatus>>;
+
+	/// hash and uri of the body of the proposal kept off-chain
+	#[rpc(name = "moloch_proposalContent")]
+	fn proposal_content(
+		&self,
+		dao_id: DaoId,
+		proposal_index: NumberOrHex,
+	) -> Result<Option<ProposalContent<Hash>>>;
+
+	#[rpc(name = "moloch_ragequitPreview")]
+	fn ragequit_preview(
+		&self,
+		dao_id: DaoId,
+		shares_to_burn: NumberOrHex,
+	) -> Result<NumberOrHex>;
+
+	#[rpc(name = "moloch_guildBank")]
+	fn guild_bank_balance(
+		&self,
+		dao_id: DaoId,
+	) -> Result<NumberOrHex>;
+}
+
+/// A struct that implements the [`MolochApi`], `P` is `(Block, Balance)` of the runtime.
+pub struct Moloch<C, P> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> Moloch<C, P> {
+	/// Create new `Moloch` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The transaction was not decodable.
+	DecodeError,
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::DecodeError => 2,
+		}
+	}
+}
+
+fn runtime_error(e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(Error::RuntimeError.into()),
+		message: "Unable to query moloch state.".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+fn from_number_or_hex(value: NumberOrHex) -> Result<u128> {
+	value.try_into().map_err(|e| RpcError {
+		code: ErrorCode::InvalidParams,
+		message: "Argument does not fit in u128.".into(),
+		data: Some(format!("{:?}", e).into()),
+	})
+}
+
+fn to_number_or_hex(value: u128) -> NumberOrHex {
+	match TryInto::<u64>::try_into(value) {
+		Ok(number) => NumberOrHex::Number(number),
+		Err(_) => NumberOrHex::Hex(value.into()),
+	}
+}
+
+impl<C, Block, AccountId, Balance, Hash> MolochApi<AccountId, Hash> for Moloch<C, (Block, Balance)>
+where
+	Block: BlockT,
+	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: MolochRuntimeApi<Block, AccountId, Balance, Hash>,
+	AccountId: Clone + Codec,
+	Balance: Codec + MaybeDisplay + Into<u128>,
+	Hash: Codec,
+{
+	fn current_period(
+		&self,
+		dao_id: DaoId,
+	) -> Result<Option<NumberOrHex>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+		api.current_period(&at, dao_id)
+			.map(|period| period.map(to_number_or_hex))
+			.map_err(runtime_error)
+	}
+
+	fn member_info(
+		&self,
+		dao_id: DaoId,
+		who: AccountId,
+	) -> Result<Option<(NumberOrHex, NumberOrHex, Option<NumberOrHex>, AccountId)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+		api.member_info(&at, dao_id, who)
+			.map(|member| member.map(|(shares, loot, highest_index_yes_vote, delegate_key)| {
+				(to_number_or_hex(shares), to_number_or_hex(loot), highest_index_yes_vote.map(to_number_or_hex), delegate_key)
+			}))
+			.map_err(runtime_error)
+	}
+
+	fn proposal_status(
+		&self,
+		dao_id: DaoId,
+		proposal_index: NumberOrHex,
+	) -> Result<Option<ProposalStatus>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+		api.proposal_status(&at, dao_id, from_number_or_hex(proposal_index)?).map_err(runtime_error)
+	}
+
+	fn proposal_content(
+		&self,
+		dao_id: DaoId,
+		proposal_index: NumberOrHex,
+	) -> Result<Option<ProposalContent<Hash>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+		api.proposal_content(&at, dao_id, from_number_or_hex(proposal_index)?).map_err(runtime_error)
+	}
+
+	fn ragequit_preview(
+		&self,
+		dao_id: DaoId,
+		shares_to_burn: NumberOrHex,
+	) -> Result<NumberOrHex> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+		api.ragequit_preview(&at, dao_id, from_number_or_hex(shares_to_burn)?)
+			.map(|balance| to_number_or_hex(balance.into()))
+			.map_err(runtime_error)
+	}
+
+	fn guild_bank_balance(
+		&self,
+		dao_id: DaoId,
+	) -> Result<NumberOrHex> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+		api.guild_bank_balance(&at, dao_id)
+			.map(|balance| to_number_or_hex(balance.into()))
+			.map_err(runtime_error)
+	}
+}
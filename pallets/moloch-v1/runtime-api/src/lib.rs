@@ -0,0 +1,23 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+// Here we declare the runtime API. It is implemented it the `impl` block in
+// runtime amalgamator file (the `runtime/src/lib.rs`)
+use codec::Codec;
+use sp_runtime::traits::MaybeDisplay;
+pub use pallet_moloch_v1::{DaoId, ProposalStatus, ProposalContent};
+
+sp_api::decl_runtime_apis! {
+	pub trait MolochApi<AccountId, Balance, Hash> where
+	AccountId: Codec,
+	Balance: Codec + MaybeDisplay,
+	Hash: Codec
+	{
+		fn current_period(dao_id: DaoId) -> Option<u128>;
+		// (shares, loot, highest_index_yes_vote, delegate_key)
+		fn member_info(dao_id: DaoId, who: AccountId) -> Option<(u128, u128, Option<u128>, AccountId)>;
+		fn proposal_status(dao_id: DaoId, proposal_index: u128) -> Option<ProposalStatus>;
+		fn proposal_content(dao_id: DaoId, proposal_index: u128) -> Option<ProposalContent<Hash>>;
+		fn ragequit_preview(dao_id: DaoId, shares_to_burn: u128) -> Balance;
+		fn guild_bank_balance(dao_id: DaoId) -> Balance;
+	}
+}
@@ -5,14 +5,22 @@
 /// https://substrate.dev/docs/en/knowledgebase/runtime/frame
 /// debug guide https://substrate.dev/recipes/runtime-printing.html
 use frame_support::{
-	decl_module, decl_storage, decl_event, decl_error, dispatch, debug, ensure,
+	decl_module, decl_storage, decl_event, decl_error, dispatch, ensure, transactional, debug, Parameter, StorageHasher, Twox128,
+	storage::{with_transaction, TransactionOutcome, unhashed, migration::{StorageIterator, take_storage_value}},
+	weights::{Weight, GetDispatchInfo, PostDispatchInfo},
 	traits::{Currency, EnsureOrigin, ReservableCurrency, OnUnbalanced, Get, BalanceStatus, ExistenceRequirement::{KeepAlive, AllowDeath}},
 };
-use sp_runtime::{ModuleId, traits::{ AccountIdConversion }};
+use sp_runtime::{
+	ModuleId, RuntimeDebug, DispatchError, helpers_128bit::multiply_by_rational,
+	traits::{ AccountIdConversion, CheckedAdd, CheckedSub, Saturating, SaturatedConversion, Zero, Dispatchable, Member as MemberT },
+	transaction_validity::{TransactionSource, TransactionValidity, TransactionPriority, ValidTransaction, InvalidTransaction},
+};
 use frame_support::codec::{Encode, Decode};
-use frame_system::{ensure_signed};
-use sp_std::{vec::Vec, convert::{TryInto}};
+use frame_system::{ensure_signed, ensure_none, offchain::{SendTransactionTypes, SubmitTransaction}};
+use sp_std::{vec::Vec, boxed::Box, marker::PhantomData, cell::Cell, convert::{TryInto}};
 use pallet_timestamp;
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
 
 #[cfg(test)]
 mod mock;
@@ -20,8 +28,170 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
-// TODO: Not support enum in storage
+/// Identifier of a guild (DAO) summoned in this pallet
+pub type DaoId = u32;
+
+/// Prefix of the storage items, used to reach the layouts `decl_storage` no longer declares
+const PALLET_PREFIX: &[u8] = b"MolochV1";
+
+/// Accounts owned by a guild, all derived as sub accounts of the pallet's `ModuleId`
 #[derive(Encode, Decode, Clone, PartialEq)]
+pub enum GuildAccount {
+	// the guild bank holding the tributes
+	GuildBank(DaoId),
+}
+
+/// What a proposal does once it passes
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+pub enum ProposalKind<AssetId> {
+	// mint shares for the applicant in exchange for the tribute
+	Membership,
+	// turn the shares of the applicant into loot and jail them
+	GuildKick,
+	// accept the token as tribute and pay it out on rage quit
+	Whitelist(AssetId),
+	// dispatch the encoded `Config::Action` from the guild's origin
+	Action(Vec<u8>),
+	// replace the settings of the guild
+	Parameters(GuildParameters),
+}
+
+impl<AssetId> Default for ProposalKind<AssetId> {
+	fn default() -> Self {
+		ProposalKind::Membership
+	}
+}
+
+impl<AssetId> ProposalKind<AssetId> {
+	pub fn category(&self) -> ProposalCategory {
+		match self {
+			ProposalKind::Membership => ProposalCategory::Membership,
+			ProposalKind::GuildKick => ProposalCategory::GuildKick,
+			ProposalKind::Whitelist(_) => ProposalCategory::Whitelist,
+			ProposalKind::Action(_) => ProposalCategory::Action,
+			ProposalKind::Parameters(_) => ProposalCategory::Parameters,
+		}
+	}
+}
+
+/// `ProposalKind` without its payload, the voting thresholds of a guild are set per category
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ProposalCategory {
+	Membership,
+	GuildKick,
+	Whitelist,
+	Action,
+	Parameters,
+}
+
+/// Votes a proposal needs to pass, in percent
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct VotingThreshold {
+	// the yes and no votes must add up to at least this much of `TotalShares`
+	pub quorum: u8,
+	// the yes votes must be more than this much of the votes cast
+	pub pass: u8,
+}
+
+impl Default for VotingThreshold {
+	// a simple majority of the votes cast, without quorum
+	fn default() -> Self {
+		VotingThreshold { quorum: 0, pass: 50 }
+	}
+}
+
+/// Settings of a guild, chosen at `summon` and changed by parameter proposals
+#[derive(Encode, Decode, Default, Clone, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct GuildParameters {
+	pub voting_period_length: u128,
+	pub grace_period_length: u128,
+	pub abort_window: u128,
+	pub dilution_bound: u128,
+	pub proposal_deposit: u128,
+	pub processing_reward: u128,
+	// categories not listed here get `VotingThreshold::default()`
+	pub thresholds: Vec<(ProposalCategory, VotingThreshold)>,
+}
+
+/// A guild formed at genesis
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct GuildGenesis<AccountId, Balance> {
+	pub period_duration: u32,
+	pub parameters: GuildParameters,
+	// (founder, shares, loot)
+	pub founders: Vec<(AccountId, u128, u128)>,
+	// minted into the guild bank
+	pub funding: Balance,
+}
+
+/// Origin of the calls dispatched by the passed action proposals of a guild
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode)]
+pub enum RawOrigin {
+	// the guild voted YES by majority
+	Dao(DaoId),
+}
+
+/// Origin for the moloch module.
+pub type Origin = RawOrigin;
+
+/// Ensures the origin is the guild `D`, i.e. the call comes from an action proposal its members passed
+pub struct EnsureMolochMajority<O, D>(PhantomData<(O, D)>);
+
+impl<O: Into<Result<RawOrigin, O>> + From<RawOrigin>, D: Get<DaoId>> EnsureOrigin<O> for EnsureMolochMajority<O, D> {
+	type Success = DaoId;
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		o.into().and_then(|o| match o {
+			RawOrigin::Dao(dao_id) if dao_id == D::get() => Ok(dao_id),
+			r => Err(O::from(r)),
+		})
+	}
+}
+
+/// Where a proposal stands in its life cycle, reported through the runtime api
+#[derive(Encode, Decode, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ProposalStatus {
+	// voting has not started yet
+	Pending,
+	// members can vote on it
+	Voting,
+	// voting has ended, members who voted NO can still ragequit
+	Grace,
+	// ready to be processed
+	Ready,
+	// the proposal has been processed
+	Processed,
+}
+
+/// Storage layout versions, used to decide which migration to run on runtime upgrade
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Releases {
+	// a single guild kept in plain storage values, its proposals in a single `ProposalQueue` vector
+	V1,
+	// proposals are stored in `Proposals` indexed by guild and proposal index
+	V2,
+	// `SummonTime` is a reading of `Config::PeriodClock` instead of a timestamp in milli seconds,
+	// and `PeriodDuration` is counted in the unit of the clock instead of in seconds
+	V3,
+	// tributes and deposits are reserved on their owners instead of held by the custody account and guild bank
+	V4,
+	// `ProposalVotes` holds a `Ballot` instead of the raw vote unit
+	V5,
+	// votes are weighted with `ShareCheckpoints` instead of the live shares
+	V6,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1
+	}
+}
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
 pub enum Vote {
 	// default value, counted as abstention
 	Null,
@@ -29,26 +199,84 @@ pub enum Vote {
 	No
 }
 
-#[derive(Encode, Decode, Default, Clone, PartialEq)]
+impl Default for Vote {
+	fn default() -> Self {
+		Vote::Null
+	}
+}
+
+/// Vote of a member on a proposal
+#[derive(Encode, Decode, Default, Clone, PartialEq, RuntimeDebug)]
+pub struct Ballot<AccountId> {
+	pub vote: Vote,
+	// own shares plus the shares of `delegators`, fixed when the vote is first cast
+	pub weight: u128,
+	// members whose delegated shares were tallied with this vote
+	pub delegators: Vec<AccountId>,
+}
+
+#[derive(Encode, Decode, Default, Clone, PartialEq, RuntimeDebug)]
 pub struct Member<AccountId> {
 	// the # of shares assigned to this member
 	pub shares: u128,
-	// highest proposal index # on which the member voted YES
-	pub highest_index_yes_vote: u128,
+	// highest proposal index # on which the member voted YES, `None` until the first YES vote
+	pub highest_index_yes_vote: Option<u128>,
 	// always true once a member has been created
 	pub exists: bool,
 	// the key responsible for submitting proposals and voting - defaults to member address unless updated
 	pub delegate_key: AccountId,
+	// the # of non-voting shares, with the same claim on the guild bank as shares
+	pub loot: u128,
+	// true once the member got kicked, jailed members can not vote or be proposed
+	pub jailed: bool,
+}
+
+/// `Member` as stored before `Releases::V2`, only used to decode the single guild layout
+#[derive(Encode, Decode, Default, Clone, PartialEq, RuntimeDebug)]
+struct LegacyMember<AccountId> {
+	shares: u128,
+	highest_index_yes_vote: u128,
+	exists: bool,
+	delegate_key: AccountId,
+}
+
+/// `Proposal` as stored before `Releases::V2`, only used to decode the single guild layout
+#[derive(Encode, Decode, Default, Clone, PartialEq, RuntimeDebug)]
+struct LegacyProposal<AccountId> {
+	proposer: AccountId,
+	applicant: AccountId,
+	shares_requested: u128,
+	starting_period: u128,
+	yes_votes: u128,
+	no_votes: u128,
+	processed: bool,
+	did_pass: bool,
+	aborted: bool,
+	token_tribute: u128,
+	details: Vec<u8>,
+	max_total_shares_at_yes: u128,
 }
 
-#[derive(Encode, Decode, Default, Clone, PartialEq)]
-pub struct Proposal<AccountId> {
+/// Body of a proposal kept off-chain, e.g. on IPFS, the chain only keeps its hash
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ProposalContent<Hash> {
+	// hash of the body, for anyone fetching it to check its integrity
+	pub hash: Hash,
+	// where to fetch the body from, e.g. `ipfs://<cid>`
+	pub uri: Vec<u8>,
+}
+
+#[derive(Encode, Decode, Default, Clone, PartialEq, RuntimeDebug)]
+pub struct Proposal<AccountId, AssetId, Hash> {
 	// the member who submitted the proposal
 	pub proposer: AccountId,
 	// the applicant who wishes to become a member - this key will be used for withdrawals
 	pub applicant: AccountId,
 	// the # of shares the applicant is requesting
 	pub shares_requested: u128,
+	// the # of loot the applicant is requesting, loot has a claim on the guild bank but no votes
+	pub loot_requested: u128,
 	// the period in which voting can start for this proposal
 	pub starting_period: u128,
 	// the total number of YES votes for this proposal
@@ -63,25 +291,140 @@ pub struct Proposal<AccountId> {
 	pub aborted: bool,
 	// amount of tokens offered as tribute
 	pub token_tribute: u128,
-	// proposal details - Must be ascii chars, limited length
+	// the whitelisted token the tribute is paid in, `None` for `Config::Currency`
+	pub tribute_token: Option<AssetId>,
+	// amount of `Config::Currency` paid from the guild bank to the applicant if the proposal passes
+	pub payment_requested: u128,
+	// proposal details - UTF-8, at most `MaxDetailsLength` bytes
 	pub details: Vec<u8>,
-	// the maximum # of total shares encountered at a yes vote on this proposal
+	// commitment to a body kept off-chain, if the details do not say it all
+	pub content: Option<ProposalContent<Hash>>,
+	// the maximum # of total shares and loot encountered at a yes vote on this proposal
 	pub max_total_shares_at_yes: u128,
+	// the deposit reserved on the sponsor, returned minus the processing reward
+	pub deposit: u128,
+	// the account the deposit is reserved on, the delegate key of the sponsor
+	pub depositor: AccountId,
+	// what the proposal does once it passes, the applicant is the member to kick for a guild kick
+	pub kind: ProposalKind<AssetId>,
+	// the voting period length of the guild when the proposal got in the queue
+	pub voting_period_length: u128,
+	// the grace period length of the guild when the proposal got in the queue
+	pub grace_period_length: u128,
+}
+
+/// Minimal `fungibles` style access to the assets a guild can hold besides `Config::Currency`
+pub trait Fungibles<AccountId> {
+	/// Identifier of an asset
+	type AssetId: Parameter + MemberT + Copy + Default;
+
+	/// Free balance of `who` in `asset`
+	fn balance(asset: Self::AssetId, who: &AccountId) -> u128;
+
+	/// Balance of `who` in `asset` set aside by `reserve`, not part of the free balance
+	fn reserved_balance(asset: Self::AssetId, who: &AccountId) -> u128;
+
+	/// Move `amount` of `asset` from `source` to `dest`
+	fn transfer(asset: Self::AssetId, source: &AccountId, dest: &AccountId, amount: u128) -> dispatch::DispatchResult;
+
+	/// Set aside `amount` of the free balance of `who`
+	fn reserve(asset: Self::AssetId, who: &AccountId, amount: u128) -> dispatch::DispatchResult;
+
+	/// Return `amount` reserved by `who` to their free balance
+	fn unreserve(asset: Self::AssetId, who: &AccountId, amount: u128) -> dispatch::DispatchResult;
+
+	/// Move `amount` reserved by `source` to the free balance of `dest`
+	fn repatriate_reserved(asset: Self::AssetId, source: &AccountId, dest: &AccountId, amount: u128) -> dispatch::DispatchResult;
+}
+
+/// No asset besides `Config::Currency`, tribute can only be paid in the native currency
+impl<AccountId> Fungibles<AccountId> for () {
+	type AssetId = u32;
+
+	fn balance(_: Self::AssetId, _: &AccountId) -> u128 {
+		0
+	}
+
+	fn reserved_balance(_: Self::AssetId, _: &AccountId) -> u128 {
+		0
+	}
+
+	fn transfer(_: Self::AssetId, _: &AccountId, _: &AccountId, _: u128) -> dispatch::DispatchResult {
+		Err(DispatchError::Other("NoAssetSupport"))
+	}
+
+	fn reserve(_: Self::AssetId, _: &AccountId, _: u128) -> dispatch::DispatchResult {
+		Err(DispatchError::Other("NoAssetSupport"))
+	}
+
+	fn unreserve(_: Self::AssetId, _: &AccountId, _: u128) -> dispatch::DispatchResult {
+		Err(DispatchError::Other("NoAssetSupport"))
+	}
+
+	fn repatriate_reserved(_: Self::AssetId, _: &AccountId, _: &AccountId, _: u128) -> dispatch::DispatchResult {
+		Err(DispatchError::Other("NoAssetSupport"))
+	}
+}
+
+/// Clock splitting the life of a guild into periods of `period_duration`
+pub trait PeriodClock {
+	/// Current reading of the clock, in the unit `period_duration` is expressed in
+	fn now() -> u128;
+
+	/// Reading of the clock at the `pallet_timestamp` moment `moment`, in milli seconds
+	fn at_timestamp(moment: u128) -> u128;
+}
+
+/// Periods measured in seconds of `pallet_timestamp`
+pub struct TimestampClock<T>(PhantomData<T>);
+
+impl<T: pallet_timestamp::Trait> PeriodClock for TimestampClock<T> {
+	fn now() -> u128 {
+		// the timestamp is in milli seconds
+		pallet_timestamp::Module::<T>::now().saturated_into::<u128>() / 1000
+	}
+
+	fn at_timestamp(moment: u128) -> u128 {
+		moment / 1000
+	}
+}
+
+/// Periods measured in blocks, not affected by the time skew of the block authors
+pub struct BlockNumberClock<T>(PhantomData<T>);
+
+impl<T: pallet_timestamp::Trait> PeriodClock for BlockNumberClock<T> {
+	fn now() -> u128 {
+		frame_system::Module::<T>::block_number().saturated_into::<u128>()
+	}
+
+	/// Estimated from the current block, blocks are taken to be `2 * MinimumPeriod` apart
+	fn at_timestamp(moment: u128) -> u128 {
+		let block_time = T::MinimumPeriod::get().saturated_into::<u128>().saturating_mul(2).max(1);
+		let now = pallet_timestamp::Module::<T>::now().saturated_into::<u128>();
+		if moment < now {
+			Self::now().saturating_sub((now - moment) / block_time)
+		} else {
+			Self::now().saturating_add((moment - now) / block_time)
+		}
+	}
 }
 
 type MemberOf<T> = Member<<T as frame_system::Trait>::AccountId>;
-type ProposalOf<T> = Proposal<<T as frame_system::Trait>::AccountId>;
+type ProposalOf<T> = Proposal<<T as frame_system::Trait>::AccountId, AssetIdOf<T>, <T as frame_system::Trait>::Hash>;
+type ProposalContentOf<T> = ProposalContent<<T as frame_system::Trait>::Hash>;
+type BallotOf<T> = Ballot<<T as frame_system::Trait>::AccountId>;
+type AssetIdOf<T> = <<T as Config>::Assets as Fungibles<<T as frame_system::Trait>::AccountId>>::AssetId;
 type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
 type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::NegativeImbalance;
 
 /// Configure the pallet by specifying the parameters and types on which it depends.
-pub trait Config: pallet_timestamp::Trait + frame_system::Trait {
+pub trait Config: pallet_timestamp::Trait + frame_system::Trait + SendTransactionTypes<Call<Self>> {
 	// used to generate sovereign account
 	// refer: https://github.com/paritytech/substrate/blob/743accbe3256de2fc615adcaa3ab03ebdbbb4dbd/frame/treasury/src/lib.rs#L92
 	type ModuleId: Get<ModuleId>;
 
 	/// Origin from which admin must come.
-	type AdminOrigin: EnsureOrigin<Self::Origin>;
+	type AdminOrigin: EnsureOrigin<<Self as frame_system::Trait>::Origin>;
 
     // The runtime must supply this pallet with an Event type that satisfies the pallet's requirements.
     type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
@@ -92,6 +435,9 @@ pub trait Config: pallet_timestamp::Trait + frame_system::Trait {
 	/// What to do with slashed funds.
 	type Slashed: OnUnbalanced<NegativeImbalanceOf<Self>>;
 
+	/// The clock periods are counted with, `period_duration` of `summon` is expressed in its unit
+	type PeriodClock: PeriodClock;
+
 	// maximum length of voting period
 	type MaxVotingPeriodLength: Get<u128>;
 
@@ -104,7 +450,32 @@ pub trait Config: pallet_timestamp::Trait + frame_system::Trait {
 	// maximum number of shares
 	type MaxShares: Get<u128>;
 
-	
+	/// The assets besides `Currency` a guild can whitelist.
+	type Assets: Fungibles<Self::AccountId>;
+
+	// maximum number of whitelisted tokens of a guild
+	type MaxWhitelistedTokens: Get<u32>;
+
+	/// The outer origin type, guilds dispatch their actions with `RawOrigin::Dao`.
+	type Origin: From<RawOrigin>;
+
+	/// The calls an action proposal can dispatch.
+	type Action: Parameter + Dispatchable<Origin = <Self as Config>::Origin, PostInfo = PostDispatchInfo> + GetDispatchInfo;
+
+	// maximum weight of the call of an action proposal
+	type MaxActionWeight: Get<Weight>;
+
+	// maximum number of members delegating their vote to the same member
+	type MaxDelegators: Get<u32>;
+
+	// maximum length in bytes of the details of a proposal, and of the uri of its content
+	type MaxDetailsLength: Get<u32>;
+
+	/// Weight `on_initialize` may spend processing ready proposals, zero turns the hook off
+	type AutoProcessWeight: Get<Weight>;
+
+	/// Priority of the unsigned `process_proposal_unsigned` submitted by the off-chain worker
+	type UnsignedPriority: Get<TransactionPriority>;
 }
 
 // The pallet's runtime storage items.
@@ -115,34 +486,66 @@ decl_storage! {
 	trait Store for Module<T: Config> as MolochV1 {
 		// Learn more about declaring storage items:
 		// https://substrate.dev/docs/en/knowledgebase/runtime/storage#declaring-storage-items
-		// Map, each round start with an id => bool 
-		TotalShares get(fn totoal_shares): u128;
-		TotalSharesRequested get(fn totoal_shares_requested): u128;
-		PeriodDuration get(fn period_duration): u32;
-        VotingPeriodLength get(fn voting_period_length): u128;
-        GracePeriodLength get(fn grace_period_length): u128;
-        AbortWindow get(fn abort_window): u128;
-        ProposalDeposit get(fn proposal_deposit): BalanceOf<T>;
-        DilutionBound get(fn dilution_bound): u128;
-        ProcessingReward get(fn processing_reward): BalanceOf<T>;
-		SummonTime get(fn summon_time): T::Moment;
-		Members get(fn members): map hasher(blake2_128_concat) T::AccountId  => MemberOf<T>;
-		AddressOfDelegates get(fn address_of_delegate): map hasher(blake2_128_concat) T::AccountId  => T::AccountId;
-		ProposalQueue get(fn proposal_queue): Vec<ProposalOf<T>>;
-		ProposalVotes get(fn proposal_vote): double_map hasher(blake2_128_concat) u128, hasher(blake2_128_concat) T::AccountId => u8;
-		ApplicantCustody get(fn applicant_custody):  map hasher(blake2_128_concat) T::AccountId  => BalanceOf<T>;
+		// Number of guilds ever summoned, also the id of the next guild
+		DaoCount get(fn dao_count): DaoId;
+		TotalShares get(fn totoal_shares): map hasher(blake2_128_concat) DaoId => u128;
+		TotalLoot get(fn total_loot): map hasher(blake2_128_concat) DaoId => u128;
+		TotalSharesRequested get(fn totoal_shares_requested): map hasher(blake2_128_concat) DaoId => u128;
+		TotalLootRequested get(fn total_loot_requested): map hasher(blake2_128_concat) DaoId => u128;
+		PeriodDuration get(fn period_duration): map hasher(blake2_128_concat) DaoId => u32;
+		VotingPeriodLength get(fn voting_period_length): map hasher(blake2_128_concat) DaoId => u128;
+		GracePeriodLength get(fn grace_period_length): map hasher(blake2_128_concat) DaoId => u128;
+		AbortWindow get(fn abort_window): map hasher(blake2_128_concat) DaoId => u128;
+		ProposalDeposit get(fn proposal_deposit): map hasher(blake2_128_concat) DaoId => BalanceOf<T>;
+		DilutionBound get(fn dilution_bound): map hasher(blake2_128_concat) DaoId => u128;
+		ProcessingReward get(fn processing_reward): map hasher(blake2_128_concat) DaoId => BalanceOf<T>;
+		// Reading of `Config::PeriodClock` when the guild was summoned
+		SummonTime get(fn summon_time): map hasher(blake2_128_concat) DaoId => u128;
+		Members get(fn members): double_map hasher(blake2_128_concat) DaoId, hasher(blake2_128_concat) T::AccountId  => MemberOf<T>;
+		AddressOfDelegates get(fn address_of_delegate): double_map hasher(blake2_128_concat) DaoId, hasher(blake2_128_concat) T::AccountId  => T::AccountId;
+		Proposals get(fn proposals): double_map hasher(blake2_128_concat) DaoId, hasher(blake2_128_concat) u128 => ProposalOf<T>;
+		// Number of proposals ever submitted to a guild, also the index of the next proposal
+		ProposalCount get(fn proposal_count): map hasher(blake2_128_concat) DaoId => u128;
+		// Proposals waiting for a member to sponsor them, indexed by their proposal id
+		UnsponsoredProposals get(fn unsponsored_proposals): double_map hasher(blake2_128_concat) DaoId, hasher(blake2_128_concat) u128 => ProposalOf<T>;
+		// Number of proposals ever submitted for sponsorship, also the id of the next one
+		UnsponsoredProposalCount get(fn unsponsored_proposal_count): map hasher(blake2_128_concat) DaoId => u128;
+		// Number of processed proposals, also the index of the next proposal to process
+		ProcessedCount get(fn processed_count): map hasher(blake2_128_concat) DaoId => u128;
+		StorageVersion get(fn storage_version) build(|_| Releases::V6): Releases;
+		ProposalVotes get(fn proposal_vote): double_map hasher(blake2_128_concat) (DaoId, u128), hasher(blake2_128_concat) T::AccountId => BallotOf<T>;
+		// Delegate whose vote carried the shares of the member, the member can not vote on the proposal any more
+		DelegatedVotes get(fn delegated_vote): double_map hasher(blake2_128_concat) (DaoId, u128), hasher(blake2_128_concat) T::AccountId => Option<T::AccountId>;
+		// Member the voting weight of a member is delegated to, until the given period
+		VoteDelegations get(fn vote_delegation): double_map hasher(blake2_128_concat) DaoId, hasher(blake2_128_concat) T::AccountId => Option<(T::AccountId, u128)>;
+		// Members who delegated to a member, at most `MaxDelegators`, expired delegations are skipped when tallying
+		Delegators get(fn delegators): double_map hasher(blake2_128_concat) DaoId, hasher(blake2_128_concat) T::AccountId => Vec<T::AccountId>;
+		// Tribute reserved by an applicant through `custody`, not yet locked by a proposal
+		ApplicantCustody get(fn applicant_custody): double_map hasher(blake2_128_concat) DaoId, hasher(blake2_128_concat) T::AccountId  => BalanceOf<T>;
+		// Sum of the tributes locked by the open proposals of an applicant, per token, `None` for `Config::Currency`
+		LockedTributes get(fn locked_tribute): double_map hasher(blake2_128_concat) (DaoId, Option<AssetIdOf<T>>), hasher(blake2_128_concat) T::AccountId => u128;
+		// `Config::Currency` reserved on an applicant as custody or locked tribute, summed over every guild
+		HeldTributes get(fn held_tributes): map hasher(blake2_128_concat) T::AccountId => BalanceOf<T>;
+		// Tokens the guild accepts as tribute besides `Config::Currency`, at most `MaxWhitelistedTokens`
+		ApprovedTokens get(fn approved_tokens): map hasher(blake2_128_concat) DaoId => Vec<AssetIdOf<T>>;
+		// Votes needed by the proposals of a category to pass
+		Thresholds get(fn thresholds): double_map hasher(blake2_128_concat) DaoId, hasher(blake2_128_concat) ProposalCategory => VotingThreshold;
+		// The guild `on_initialize` looks at first in the next block
+		AutoProcessCursor get(fn auto_process_cursor): DaoId;
+		// Proposal `on_initialize` failed to process, it waits for `process_proposal`
+		StuckProposal get(fn stuck_proposal): map hasher(blake2_128_concat) DaoId => Option<u128>;
+		// Shares of a member as `(period, shares)`, held from the end of the period on, oldest first
+		ShareCheckpoints get(fn share_checkpoints): double_map hasher(blake2_128_concat) DaoId, hasher(blake2_128_concat) T::AccountId => Vec<(u128, u128)>;
+		// History of `TotalShares`, kept like `ShareCheckpoints`
+		TotalShareCheckpoints get(fn total_share_checkpoints): map hasher(blake2_128_concat) DaoId => Vec<(u128, u128)>;
 	}
 	add_extra_genesis {
-		build(|_config| {
-			// Create pallet's internal account
-			let _ = T::Currency::make_free_balance_be(
-				&<Module<T>>::account_id(),
-				T::Currency::minimum_balance(),
-			);
-			let _ = T::Currency::make_free_balance_be(
-				&<Module<T>>::custody_account(),
-				T::Currency::minimum_balance(),
-			);
+		config(guilds): Vec<GuildGenesis<T::AccountId, BalanceOf<T>>>;
+		build(|config| {
+			for guild in &config.guilds {
+				Module::<T>::do_summon(None, guild.period_duration, &guild.parameters, &guild.founders, guild.funding)
+					.expect("genesis guilds must be valid");
+			}
 		});
 	}
 }
@@ -151,27 +554,75 @@ decl_storage! {
 // https://substrate.dev/docs/en/knowledgebase/runtime/events
 decl_event!(
 	pub enum Event<T> where AccountId = <T as frame_system::Trait>::AccountId, 
-	        Balance = <<T as Config>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance {
+	        Balance = <<T as Config>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance,
+	        AssetId = <<T as Config>::Assets as Fungibles<<T as frame_system::Trait>::AccountId>>::AssetId {
 		/// Event documentation should end with an array that provides descriptive names for event
-		/// parameters. [proposalIndex, delegateKey, memberAddress, applicant, tokenTribute, sharesRequested] 
-		SubmitProposal(u128, AccountId, AccountId, AccountId, u128, u128),
-		/// parameters. [proposalIndex, delegateKey, memberAddress, uintVote]
-		SubmitVote(u128, AccountId, AccountId, u8),
-		/// parameters. [proposalIndex, applicant, memberAddress, tokenTribute, sharesRequested, didPass]
-		ProcessProposal(u128, AccountId, AccountId, u128, u128, bool),
-		/// parameters. [memberAddress, sharesToBurn]
-		Ragequit(AccountId, u128),
-		/// parameters. [proposalIndex, applicantAddress]
-		Abort(u128, AccountId),
-		/// parameters. [memberAddress, newDelegateKey]
-		UpdateDelegateKey(AccountId, AccountId),
-		/// parameters. [summoner, shares]
-		SummonComplete(AccountId, u128),
-		/// parameters. [totalShares, dilutionBond, maxTotalSharesVoteAtYes]
-		DilutionBoundExeceeds(u128, u128, u128),
-		/// parameters. [currentReserved, requiredReserved]
-		CustodyBalanceOutage(Balance, Balance),
-		CustodySucceeded(AccountId, Balance),
+		/// parameters. [daoId, proposalIndex, delegateKey, memberAddress, applicant, tokenTribute, sharesRequested, lootRequested] 
+		SubmitProposal(DaoId, u128, AccountId, AccountId, AccountId, u128, u128, u128),
+		/// parameters. [daoId, proposalId, applicant, tokenTribute, sharesRequested, lootRequested, paymentRequested]
+		Propose(DaoId, u128, AccountId, u128, u128, u128, u128),
+		/// parameters. [daoId, proposalId, applicant, tributeToken, tokenTribute, sharesRequested, lootRequested, paymentRequested]
+		ProposeWithToken(DaoId, u128, AccountId, AssetId, u128, u128, u128, u128),
+		/// parameters. [daoId, proposalId, proposalIndex, delegateKey, memberAddress]
+		SponsorProposal(DaoId, u128, u128, AccountId, AccountId),
+		/// parameters. [daoId, proposalId, proposer]
+		CancelProposal(DaoId, u128, AccountId),
+		/// parameters. [daoId, proposalIndex, delegateKey, memberAddress, uintVote]
+		SubmitVote(DaoId, u128, AccountId, AccountId, u8),
+		/// parameters. [daoId, proposalIndex, applicant, memberAddress, tokenTribute, sharesRequested, lootRequested, didPass, quorumReached, thresholdReached]
+		ProcessProposal(DaoId, u128, AccountId, AccountId, u128, u128, u128, bool, bool, bool),
+		/// parameters. [daoId, memberAddress, sharesToBurn, lootToBurn]
+		Ragequit(DaoId, AccountId, u128, u128),
+		/// parameters. [daoId, proposalIndex, delegateKey, memberAddress, memberToKick]
+		SubmitGuildKick(DaoId, u128, AccountId, AccountId, AccountId),
+		/// parameters. [daoId, proposalIndex, delegateKey, memberAddress, tokenToWhitelist]
+		SubmitWhitelistProposal(DaoId, u128, AccountId, AccountId, AssetId),
+		/// parameters. [daoId, token]
+		TokenWhitelisted(DaoId, AssetId),
+		/// parameters. [daoId, proposalIndex, delegateKey, memberAddress]
+		SubmitActionProposal(DaoId, u128, AccountId, AccountId),
+		/// parameters. [daoId, proposalIndex, result]
+		ActionExecuted(DaoId, u128, dispatch::DispatchResult),
+		/// parameters. [daoId, proposalIndex, delegateKey, memberAddress]
+		SubmitParametersProposal(DaoId, u128, AccountId, AccountId),
+		/// parameters. [daoId, proposalIndex]
+		ParametersUpdated(DaoId, u128),
+		/// parameters. [daoId, memberAddress, lootToBurn]
+		Ragekick(DaoId, AccountId, u128),
+		/// parameters. [daoId, proposalIndex, applicantAddress]
+		Abort(DaoId, u128, AccountId),
+		/// parameters. [daoId, memberAddress, newDelegateKey]
+		UpdateDelegateKey(DaoId, AccountId, AccountId),
+		/// parameters. [daoId, summoner, shares]
+		SummonComplete(DaoId, AccountId, u128),
+		/// parameters. [daoId, totalShares, dilutionBond, maxTotalSharesVoteAtYes]
+		DilutionBoundExeceeds(DaoId, u128, u128, u128),
+		/// parameters. [daoId, currentReserved, requiredReserved]
+		CustodyBalanceOutage(DaoId, Balance, Balance),
+		/// parameters. [daoId, applicant, tokenTribute]
+		CustodySucceeded(DaoId, AccountId, Balance),
+		/// parameters. [daoId, applicant, amount]
+		CustodyWithdrawn(DaoId, AccountId, Balance),
+		/// parameters. [daoId, token, currentCustody, requiredCustody]
+		TokenCustodyOutage(DaoId, AssetId, u128, u128),
+		/// parameters. [daoId, availableInGuildBank, paymentRequested]
+		GuildBankBalanceOutage(DaoId, Balance, Balance),
+		/// parameters. [daoId, proposalIndex, applicant, paymentRequested]
+		PaymentTransferred(DaoId, u128, AccountId, Balance),
+		/// parameters. [daoId, proposalIndex, error]
+		AutoProcessFailed(DaoId, u128, DispatchError),
+		/// parameters. [daoId, proposalIndex, delegateKey, memberAddress, uintVote]
+		VoteChanged(DaoId, u128, AccountId, AccountId, u8),
+		/// parameters. [daoId, memberAddress, delegateTo, untilPeriod]
+		VoteDelegated(DaoId, AccountId, AccountId, u128),
+		/// parameters. [daoId, memberAddress]
+		VoteUndelegated(DaoId, AccountId),
+		/// parameters. [daoId, proposalIndex, delegateKey]
+		CancelQueuedProposal(DaoId, u128, AccountId),
+		/// parameters. [daoId, proposalIndex]
+		ProposalExpired(DaoId, u128),
+		/// parameters. [daoId, memberAddress, revokedDelegateKey]
+		DelegateKeyRevoked(DaoId, AccountId, AccountId),
 	}
 );
 
@@ -201,10 +652,62 @@ decl_error! {
 		ProposalExpired,
 		InvalidVote,
 		MemberHasVoted,
+		/// The member delegated their vote, or their shares were tallied in the vote of their delegate
+		VoteDelegated,
+		/// Votes can only be delegated to another member, who does not delegate their own vote
+		InvalidDelegate,
+		/// The member already holds `MaxDelegators` delegations
+		TooManyDelegators,
 		AbortWindowHasPassed,
 		NoOverwriteDelegate,
 		NoOverwriteMember,
 		NoCustodyFound,
+		/// No guild has been summoned with this id
+		DaoNotExist,
+		/// Only the proposer can cancel the proposal
+		NotProposalProposer,
+		/// The member has been kicked out of the guild
+		MemberJailed,
+		/// The member has no loot to withdraw
+		NoEnoughLoot,
+		/// Only membership proposals can be aborted by the applicant
+		ProposalNotAbortable,
+		/// The token is not whitelisted by the guild
+		TokenNotWhitelisted,
+		/// The token is already whitelisted by the guild
+		TokenAlreadyWhitelisted,
+		/// The guild can not whitelist more than `MaxWhitelistedTokens`
+		TooManyTokens,
+		/// The call of an action proposal weighs more than `MaxActionWeight`
+		ActionTooHeavy,
+		/// The call of an action proposal can not be decoded any more
+		InvalidAction,
+		/// An arithmetic operation overflowed
+		Overflow,
+		/// An arithmetic operation went below zero
+		Underflow,
+		/// The amount does not fit in the balance type of the runtime
+		BalanceConversion,
+		/// The period clock is behind the summon time of the guild
+		PeriodNotReached,
+		/// The period duration of a guild must not be zero
+		InvalidPeriodDuration,
+		/// A quorum above 100 percent, a pass threshold of 100 percent or a category set twice
+		InvalidThreshold,
+		/// Voting on the proposal has started already
+		ProposalHasStarted,
+		/// The next proposal in queue can still pass
+		NoStaleProposal,
+		/// The details or the content uri of a proposal are longer than `MaxDetailsLength`
+		DetailsTooLong,
+		/// The details of a proposal must be UTF-8, the content uri printable ASCII
+		InvalidDetails,
+		/// The member acts through their member address, there is no delegate key to revoke
+		NoDelegateKey,
+		/// Only the loot of a member kicked out of the guild can be withdrawn by anyone
+		MemberNotJailed,
+		/// Less than the custody and the tributes the guilds hold for the applicant is still reserved on them
+		TributeNotReserved,
 	}
 }
 
@@ -212,7 +715,7 @@ decl_error! {
 // These functions materialize as "extrinsics", which are often compared to transactions.
 // Dispatchable functions must be annotated with a weight and must return a DispatchResult.
 decl_module! {
-	pub struct Module<T: Config> for enum Call where origin: T::Origin {
+	pub struct Module<T: Config> for enum Call where origin: <T as frame_system::Trait>::Origin {
 		// Errors must be initialized if they are used by the pallet.
 		type Error = Error<T>;
 
@@ -222,365 +725,1882 @@ decl_module! {
 		const MaxGracePeriodLength: u128 = T::MaxGracePeriodLength::get();
 		const MaxDilutionBound: u128 = T::MaxDilutionBound::get();
 		const MaxShares: u128 = T::MaxShares::get();
+		const MaxWhitelistedTokens: u32 = T::MaxWhitelistedTokens::get();
+		const MaxActionWeight: Weight = T::MaxActionWeight::get();
+		const MaxDelegators: u32 = T::MaxDelegators::get();
+		const MaxDetailsLength: u32 = T::MaxDetailsLength::get();
+		const AutoProcessWeight: Weight = T::AutoProcessWeight::get();
+
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			Self::auto_process(T::AutoProcessWeight::get())
+		}
+
+		fn offchain_worker(_now: T::BlockNumber) {
+			for dao_id in 0..DaoCount::get() {
+				if let Some(proposal_index) = Self::next_unattended_proposal(dao_id) {
+					let call = Call::process_proposal_unsigned(dao_id, proposal_index);
+					if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_err() {
+						debug::warn!("moloch: failed to submit the processing of proposal {} of guild {}", proposal_index, dao_id);
+					}
+				}
+			}
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let mut weight = 0;
+			if StorageVersion::get() == Releases::V1 {
+				weight += Self::migrate_to_multiple_guilds();
+				weight += Self::migrate_to_indexed_proposals();
+			}
+			if StorageVersion::get() == Releases::V2 {
+				weight += Self::migrate_summon_time();
+			}
+			if StorageVersion::get() == Releases::V3 {
+				weight += Self::migrate_custody_to_reserves();
+			}
+			if StorageVersion::get() == Releases::V4 {
+				weight += Self::migrate_votes_to_ballots();
+			}
+			if StorageVersion::get() == Releases::V5 {
+				weight += Self::migrate_share_checkpoints();
+			}
+			weight
+		}
 		
-		/// Summon a group or orgnization
+		/// Summon a new group or orgnization, the guild gets a fresh `DaoId` and its own guild bank
 		#[weight = 10_000 + T::DbWeight::get().reads_writes(1,1)]
+		#[transactional]
 		pub fn summon(origin, period_duration: u32, voting_period_length: u128,
 			          grace_period_length: u128, abort_window: u128, dilution_bound: u128,
 					  #[compact] proposal_deposit: BalanceOf<T>, 
 					  #[compact]  processing_reward: BalanceOf<T>) -> dispatch::DispatchResult {
 			let who = ensure_signed(origin)?;
-			ensure!(voting_period_length <= T::MaxVotingPeriodLength::get(), Error::<T>::VotingPeriodLengthTooBig);
-			ensure!(grace_period_length <= T::MaxGracePeriodLength::get(), Error::<T>::GracePeriodLengthTooBig);
-			ensure!(dilution_bound <= T::MaxDilutionBound::get(), Error::<T>::DilutionBoundTooBig);
-			ensure!(abort_window <= voting_period_length, Error::<T>::AbortWindowTooBig);
-			ensure!(proposal_deposit >= processing_reward, Error::<T>::NoEnoughProposalDeposit);
-
-			SummonTime::<T>::put(pallet_timestamp::Module::<T>::now());
-			PeriodDuration::put(period_duration);
-			VotingPeriodLength::put(voting_period_length);
-			GracePeriodLength::put(grace_period_length);
-			AbortWindow::put(abort_window);
-			DilutionBound::put(dilution_bound);
-
-			ProposalDeposit::<T>::put(proposal_deposit);
-			ProcessingReward::<T>::put(processing_reward);
-			let member = Member {
-				shares: 1,
-				highest_index_yes_vote: 0,
-				exists: true,
-				delegate_key: who.clone(),
+			let parameters = GuildParameters {
+				voting_period_length: voting_period_length,
+				grace_period_length: grace_period_length,
+				abort_window: abort_window,
+				dilution_bound: dilution_bound,
+				proposal_deposit: Self::balance_to_u128(proposal_deposit)?,
+				processing_reward: Self::balance_to_u128(processing_reward)?,
+				thresholds: Vec::new(),
 			};
-			Members::<T>::insert(who.clone(), member);
-			AddressOfDelegates::<T>::insert(who.clone(), who.clone());
-			TotalShares::put(1);
-			Self::deposit_event(RawEvent::SummonComplete(who, 1));
+			let dao_id = Self::do_summon(Some(&who), period_duration, &parameters, &[(who.clone(), 1, 0)], Zero::zero())?;
+			Self::deposit_event(RawEvent::SummonComplete(dao_id, who, 1));
 			Ok(())
 		}
 
-		/// Applicant transfer tribute to custody account in advance
-		#[weight = 10_000 + T::DbWeight::get().reads_writes(1,1)]
-		pub fn custody(origin, #[compact] token_tribute: BalanceOf<T>)  -> dispatch::DispatchResult {
+		/// Summon a new guild with several founders, the caller funds the guild bank with `funding`
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 2 + 2 * founders.len() as Weight)]
+		#[transactional]
+		pub fn summon_with(origin, period_duration: u32, voting_period_length: u128,
+			               grace_period_length: u128, abort_window: u128, dilution_bound: u128,
+			               #[compact] proposal_deposit: BalanceOf<T>, #[compact] processing_reward: BalanceOf<T>,
+			               founders: Vec<(T::AccountId, u128, u128)>, #[compact] funding: BalanceOf<T>) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			let parameters = GuildParameters {
+				voting_period_length: voting_period_length,
+				grace_period_length: grace_period_length,
+				abort_window: abort_window,
+				dilution_bound: dilution_bound,
+				proposal_deposit: Self::balance_to_u128(proposal_deposit)?,
+				processing_reward: Self::balance_to_u128(processing_reward)?,
+				thresholds: Vec::new(),
+			};
+			let dao_id = Self::do_summon(Some(&who), period_duration, &parameters, &founders, funding)?;
+			for (founder, shares, _) in founders {
+				Self::deposit_event(RawEvent::SummonComplete(dao_id, founder, shares));
+			}
+			Ok(())
+		}
+
+		/// Applicant reserves the tribute in advance, on top of the custody not used by a proposal yet
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(2,2)]
+		#[transactional]
+		pub fn custody(origin, dao_id: DaoId, #[compact] token_tribute: BalanceOf<T>)  -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_dao(dao_id)?;
+			let custody_balance = ApplicantCustody::<T>::get(dao_id, who.clone())
+				.checked_add(&token_tribute)
+				.ok_or(Error::<T>::StorageOverflow)?;
+			T::Currency::reserve(&who, token_tribute)?;
+			Self::hold_tribute(&who, token_tribute)?;
+			ApplicantCustody::<T>::insert(dao_id, who.clone(), custody_balance);
+			Self::deposit_event(RawEvent::CustodySucceeded(dao_id, who.clone(), token_tribute));
+			Ok(())
+		}
+
+		/// Applicant takes back custody which is not locked by any proposal
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(2,2)]
+		#[transactional]
+		pub fn withdraw_custody(origin, dao_id: DaoId, #[compact] amount: BalanceOf<T>)  -> dispatch::DispatchResult {
 			let who = ensure_signed(origin)?;
-			let _ =  T::Currency::transfer(&who, &Self::custody_account(), token_tribute, KeepAlive);
-			ApplicantCustody::<T>::insert(who.clone(), token_tribute);
-			Self::deposit_event(RawEvent::CustodySucceeded(who.clone(), token_tribute));
+			Self::ensure_dao(dao_id)?;
+			let custody_balance = ApplicantCustody::<T>::get(dao_id, who.clone())
+				.checked_sub(&amount)
+				.ok_or(Error::<T>::NoCustodyFound)?;
+			// a slashed reserve would otherwise release the tributes locked by proposals, in this guild or another one
+			ensure!(T::Currency::reserved_balance(&who) >= HeldTributes::<T>::get(&who), Error::<T>::TributeNotReserved);
+			T::Currency::unreserve(&who, amount);
+			Self::release_tribute(&who, amount);
+			if custody_balance.is_zero() {
+				ApplicantCustody::<T>::remove(dao_id, who.clone());
+			} else {
+				ApplicantCustody::<T>::insert(dao_id, who.clone(), custody_balance);
+			}
+			Self::deposit_event(RawEvent::CustodyWithdrawn(dao_id, who, amount));
 			Ok(())
 		}
 
 
 		/// One of the members submit a proposal
 		#[weight = 10_000 + T::DbWeight::get().reads_writes(1,1)]
-		pub fn submit_proposal(origin, applicant: T::AccountId, #[compact] token_tribute: BalanceOf<T>,
-			                   shares_requested: u128, details: Vec<u8>) -> dispatch::DispatchResult {
+		#[transactional]
+		pub fn submit_proposal(origin, dao_id: DaoId, applicant: T::AccountId, #[compact] token_tribute: BalanceOf<T>,
+			                   shares_requested: u128, loot_requested: u128, details: Vec<u8>, content: Option<ProposalContentOf<T>>) -> dispatch::DispatchResult {
 			let who = ensure_signed(origin)?;
-			ensure!(AddressOfDelegates::<T>::contains_key(who.clone()), Error::<T>::NotMember);
-			let delegate = AddressOfDelegates::<T>::get(who.clone());
-			ensure!(Members::<T>::get(delegate.clone()).shares > 0, Error::<T>::NoEnoughShares);
-			let total_requested = TotalSharesRequested::get().checked_add(shares_requested).unwrap();
-			let future_shares = TotalShares::get().checked_add(total_requested).unwrap();
+			Self::ensure_dao(dao_id)?;
+			Self::ensure_details(&details, &content)?;
+			ensure!(AddressOfDelegates::<T>::contains_key(dao_id, who.clone()), Error::<T>::NotMember);
+			let delegate = AddressOfDelegates::<T>::get(dao_id, who.clone());
+			ensure!(Members::<T>::get(dao_id, delegate.clone()).shares > 0, Error::<T>::NoEnoughShares);
+			let total_requested = TotalSharesRequested::get(dao_id).checked_add(shares_requested).ok_or(Error::<T>::SharesOverFlow)?;
+			let total_loot_requested = TotalLootRequested::get(dao_id).checked_add(loot_requested).ok_or(Error::<T>::SharesOverFlow)?;
+			// shares and loot share the same cap
+			let future_shares = TotalShares::get(dao_id).checked_add(total_requested)
+				.and_then(|shares| shares.checked_add(TotalLoot::get(dao_id)))
+				.and_then(|shares| shares.checked_add(total_loot_requested))
+				.ok_or(Error::<T>::SharesOverFlow)?;
 			ensure!(future_shares <= T::MaxShares::get(), Error::<T>::SharesOverFlow);
-			// check custody record and balance
-			let custody_balance = T::Currency::free_balance(&Self::custody_account());
-			ensure!(
-				ApplicantCustody::<T>::contains_key(applicant.clone()) &&
-				custody_balance >= token_tribute,
-				Error::<T>::NoCustodyFound
-			);
+			ensure!(!Members::<T>::get(dao_id, applicant.clone()).jailed, Error::<T>::MemberJailed);
+			// the tribute comes out of the applicant's unused custody, and stays locked by this proposal
+			ensure!(ApplicantCustody::<T>::contains_key(dao_id, applicant.clone()), Error::<T>::NoCustodyFound);
+			let custody_balance = ApplicantCustody::<T>::get(dao_id, applicant.clone())
+				.checked_sub(&token_tribute)
+				.ok_or(Error::<T>::NoCustodyFound)?;
+			let token_tribute_num = Self::balance_to_u128(token_tribute)?;
 
-			// update total shares requested
-			TotalSharesRequested::put(total_requested);
-			// collect proposal deposit from proposer and store it in the Moloch until the proposal is processed
-			let _ = T::Currency::transfer(&who, &Self::account_id(), ProposalDeposit::<T>::get(), KeepAlive);
+			// update total shares and loot requested
+			TotalSharesRequested::insert(dao_id, total_requested);
+			TotalLootRequested::insert(dao_id, total_loot_requested);
+			ApplicantCustody::<T>::insert(dao_id, applicant.clone(), custody_balance);
+			Self::lock_tribute(dao_id, &applicant, None, token_tribute_num)?;
+			// reserve the proposal deposit of the proposer until the proposal is processed
+			T::Currency::reserve(&who, ProposalDeposit::<T>::get(dao_id))?;
 
-			let proposal_queue = ProposalQueue::<T>::get();
-			let proposal_period = match proposal_queue.len() {
-				0 => 0,
-				n => proposal_queue[n-1].starting_period
-			};
-			let starting_period = proposal_period.max(Self::get_current_period()).checked_add(1).unwrap();
-			let token_tribute_num = Self::balance_to_u128(token_tribute);
 			let proposal = Proposal {
 				proposer: delegate.clone(),
 				applicant: applicant.clone(),
 				shares_requested: shares_requested,
-				starting_period: starting_period,
+				loot_requested: loot_requested,
+				starting_period: 0,
 				yes_votes: 0,
 				no_votes: 0,
 				processed: false,
 				did_pass: false,
 				aborted: false,
 				token_tribute: token_tribute_num,
+				tribute_token: None,
+				payment_requested: 0,
 				details: details,
-				max_total_shares_at_yes: 0
+				content: content,
+				max_total_shares_at_yes: 0,
+				kind: ProposalKind::Membership,
+				// the deposit is recorded when the proposal gets in the queue
+				..Default::default()
 			};
-			ProposalQueue::<T>::append(proposal);
-			let proposal_index = TryInto::<u128>::try_into(ProposalQueue::<T>::get().len() - 1).ok().unwrap();
-			Self::deposit_event(RawEvent::SubmitProposal(proposal_index, who, delegate, applicant, token_tribute_num, shares_requested));
+			let proposal_index = Self::enqueue_proposal(dao_id, proposal, &who)?;
+			Self::deposit_event(RawEvent::SubmitProposal(dao_id, proposal_index, who, delegate, applicant, token_tribute_num, shares_requested, loot_requested));
 			Ok(())
 		}
 
-		/// One of the members submit a vote
+		/// One of the members proposes to kick a member out of the guild
 		#[weight = 10_000 + T::DbWeight::get().reads_writes(1,1)]
-		pub fn submit_vote(origin, proposal_index: u128, vote_unit: u8) -> dispatch::DispatchResult {
+		#[transactional]
+		pub fn submit_guild_kick(origin, dao_id: DaoId, member_to_kick: T::AccountId, details: Vec<u8>, content: Option<ProposalContentOf<T>>) -> dispatch::DispatchResult {
 			let who = ensure_signed(origin)?;
-			ensure!(AddressOfDelegates::<T>::contains_key(who.clone()), Error::<T>::NotMember);
-			let delegate = AddressOfDelegates::<T>::get(who.clone());
-			let member = Members::<T>::get(delegate.clone());
+			Self::ensure_dao(dao_id)?;
+			Self::ensure_details(&details, &content)?;
+			ensure!(AddressOfDelegates::<T>::contains_key(dao_id, who.clone()), Error::<T>::NotMember);
+			let delegate = AddressOfDelegates::<T>::get(dao_id, who.clone());
+			ensure!(Members::<T>::get(dao_id, delegate.clone()).shares > 0, Error::<T>::NoEnoughShares);
+			ensure!(Members::<T>::contains_key(dao_id, member_to_kick.clone()), Error::<T>::NotMember);
+			let member = Members::<T>::get(dao_id, member_to_kick.clone());
 			ensure!(member.shares > 0, Error::<T>::NoEnoughShares);
-			
-			let proposal_len = ProposalQueue::<T>::get().len();
-			ensure!(proposal_index < proposal_len.try_into().unwrap(), Error::<T>::ProposalNotExist);
-			let _usize_proposal_index = TryInto::<usize>::try_into(proposal_index).ok().unwrap();
-			let proposal = &mut ProposalQueue::<T>::get()[_usize_proposal_index];
-			ensure!(vote_unit < 3 && vote_unit > 0, Error::<T>::InvalidVote);
-			ensure!(Self::get_current_period() >= proposal.starting_period, Error::<T>::ProposalNotStart);
-			ensure!(
-				Self::get_current_period() <  VotingPeriodLength::get() + proposal.starting_period,
-				Error::<T>::ProposalExpired
-			);
-			ensure!(!ProposalVotes::<T>::contains_key(proposal_index, delegate.clone()), Error::<T>::MemberHasVoted);
-			ensure!(!proposal.aborted, Error::<T>::ProposalHasAborted);
-			let vote = match vote_unit {
-				1 => Vote::Yes,
-				2 => Vote::No,
-				_ => Vote::Null
+			ensure!(!member.jailed, Error::<T>::MemberJailed);
+
+			// reserve the proposal deposit of the proposer until the proposal is processed
+			T::Currency::reserve(&who, ProposalDeposit::<T>::get(dao_id))?;
+			let proposal = Proposal {
+				proposer: delegate.clone(),
+				applicant: member_to_kick.clone(),
+				details: details,
+				content: content,
+				kind: ProposalKind::GuildKick,
+				..Default::default()
 			};
-			ProposalVotes::<T>::insert(proposal_index, delegate.clone(), vote_unit);
-			// update proposal
-			if vote == Vote::Yes {
-				proposal.yes_votes = proposal.yes_votes.checked_add(member.shares).unwrap();
-				if proposal_index > member.highest_index_yes_vote {	
-					Members::<T>::mutate(delegate.clone(), |mem| {
-						mem.highest_index_yes_vote = proposal_index;
-					});
-				}
-				if TotalShares::get() > proposal.max_total_shares_at_yes {
-					proposal.max_total_shares_at_yes = TotalShares::get();
-				}
-			} else if vote == Vote::No {
-				proposal.no_votes = proposal.no_votes.checked_add(member.shares).unwrap();
-			}
-			// need to mutate for update
-			ProposalQueue::<T>::mutate(|ps| {
-				ps[_usize_proposal_index] = proposal.clone();
-			});
-			Self::deposit_event(RawEvent::SubmitVote(proposal_index, who, delegate, vote_unit));
+			let proposal_index = Self::enqueue_proposal(dao_id, proposal, &who)?;
+			Self::deposit_event(RawEvent::SubmitGuildKick(dao_id, proposal_index, who, delegate, member_to_kick));
 			Ok(())
 		}
 
-		/// Process a proposal in queue
+		/// One of the members proposes to accept a token as tribute and hold it in the guild bank
 		#[weight = 10_000 + T::DbWeight::get().reads_writes(1,1)]
-		pub fn process_proposal(origin, proposal_index: u128) -> dispatch::DispatchResult {
+		#[transactional]
+		pub fn submit_whitelist_proposal(origin, dao_id: DaoId, token: AssetIdOf<T>, details: Vec<u8>, content: Option<ProposalContentOf<T>>) -> dispatch::DispatchResult {
 			let who = ensure_signed(origin)?;
-			let proposal_len = ProposalQueue::<T>::get().len();
-			ensure!(proposal_index < proposal_len.try_into().unwrap(), Error::<T>::ProposalNotExist);
-			let _usize_proposal_index = TryInto::<usize>::try_into(proposal_index).ok().unwrap();
-			let proposal = &mut ProposalQueue::<T>::get()[_usize_proposal_index];
-			ensure!(
-				Self::get_current_period() - VotingPeriodLength::get() - GracePeriodLength::get() >= proposal.starting_period,
-				Error::<T>::ProposalNotReady
-			);
-			ensure!(proposal.processed == false, Error::<T>::ProposalHasProcessed);
-			ensure!(proposal_index == 0 || ProposalQueue::<T>::get()[_usize_proposal_index-1].processed, Error::<T>::PreviousProposalNotProcessed);
-
-			proposal.processed = true;
-			let total_requested = TotalSharesRequested::get().checked_sub(proposal.shares_requested).unwrap();
-			TotalSharesRequested::put(total_requested);
-			let mut did_pass = proposal.yes_votes > proposal.no_votes;
-			let token_tribute = Self::u128_to_balance(proposal.token_tribute);
-
-			// as anyone can process the proposal and get rewarded, so do not fail here
-			if TotalShares::get().checked_mul(DilutionBound::get()).unwrap() < proposal.max_total_shares_at_yes {
-				Self::deposit_event(RawEvent::DilutionBoundExeceeds(TotalShares::get(), DilutionBound::get(), proposal.max_total_shares_at_yes));
-				did_pass = false;
-			}
+			Self::ensure_dao(dao_id)?;
+			Self::ensure_details(&details, &content)?;
+			ensure!(AddressOfDelegates::<T>::contains_key(dao_id, who.clone()), Error::<T>::NotMember);
+			let delegate = AddressOfDelegates::<T>::get(dao_id, who.clone());
+			ensure!(Members::<T>::get(dao_id, delegate.clone()).shares > 0, Error::<T>::NoEnoughShares);
+			let approved_tokens = ApprovedTokens::<T>::get(dao_id);
+			ensure!(!approved_tokens.contains(&token), Error::<T>::TokenAlreadyWhitelisted);
+			ensure!((approved_tokens.len() as u32) < T::MaxWhitelistedTokens::get(), Error::<T>::TooManyTokens);
 
-			// check custody record and balance
-			let custody_balance = T::Currency::free_balance(&Self::custody_account());
-			if !ApplicantCustody::<T>::contains_key(&proposal.applicant) || custody_balance < token_tribute {
-				Self::deposit_event(RawEvent::CustodyBalanceOutage(custody_balance, token_tribute));
-				did_pass = false;
-			}
+			// reserve the proposal deposit of the proposer until the proposal is processed
+			T::Currency::reserve(&who, ProposalDeposit::<T>::get(dao_id))?;
+			let proposal = Proposal {
+				proposer: delegate.clone(),
+				applicant: delegate.clone(),
+				details: details,
+				content: content,
+				kind: ProposalKind::Whitelist(token),
+				..Default::default()
+			};
+			let proposal_index = Self::enqueue_proposal(dao_id, proposal, &who)?;
+			Self::deposit_event(RawEvent::SubmitWhitelistProposal(dao_id, proposal_index, who, delegate, token));
+			Ok(())
+		}
 
-			// Proposal passed
-			if did_pass && !proposal.aborted {
-				proposal.did_pass = true;
+		/// One of the members proposes new settings for the guild, within the bounds of `Config`
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1,1)]
+		#[transactional]
+		pub fn submit_parameters_proposal(origin, dao_id: DaoId, voting_period_length: u128, grace_period_length: u128,
+			                              abort_window: u128, dilution_bound: u128, #[compact] proposal_deposit: BalanceOf<T>,
+			                              #[compact] processing_reward: BalanceOf<T>, thresholds: Vec<(ProposalCategory, VotingThreshold)>,
+			                              details: Vec<u8>, content: Option<ProposalContentOf<T>>) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_dao(dao_id)?;
+			Self::ensure_details(&details, &content)?;
+			ensure!(AddressOfDelegates::<T>::contains_key(dao_id, who.clone()), Error::<T>::NotMember);
+			let delegate = AddressOfDelegates::<T>::get(dao_id, who.clone());
+			ensure!(Members::<T>::get(dao_id, delegate.clone()).shares > 0, Error::<T>::NoEnoughShares);
+			let parameters = GuildParameters {
+				voting_period_length: voting_period_length,
+				grace_period_length: grace_period_length,
+				abort_window: abort_window,
+				dilution_bound: dilution_bound,
+				proposal_deposit: Self::balance_to_u128(proposal_deposit)?,
+				processing_reward: Self::balance_to_u128(processing_reward)?,
+				thresholds: thresholds,
+			};
+			Self::ensure_parameters(&parameters)?;
 
-				// if the applicant is already a member, add to their existing shares
-				if Members::<T>::contains_key(&proposal.applicant) {
-					Members::<T>::mutate(&proposal.applicant, |mem| {
-						mem.shares = mem.shares.checked_add(proposal.shares_requested).unwrap();
-					});
-				} else {
-					// if the applicant address is already taken by a member's delegateKey, reset it to their member address
-					if AddressOfDelegates::<T>::contains_key(proposal.applicant.clone()) {
-						let delegate = AddressOfDelegates::<T>::get(proposal.applicant.clone());
-						Members::<T>::mutate(delegate.clone(), |mem| {
-							mem.delegate_key = delegate.clone();
-						});
-						AddressOfDelegates::<T>::insert(delegate.clone(), delegate.clone());
-					}
-					// add new member
-					let member = Member {
-						shares: proposal.shares_requested,
-						highest_index_yes_vote: 0,
-						exists: true,
-						delegate_key: proposal.applicant.clone(),
-					};
-					Members::<T>::insert(proposal.applicant.clone(), member);
-					AddressOfDelegates::<T>::insert(proposal.applicant.clone(), proposal.applicant.clone());
-				}
+			// reserve the proposal deposit of the proposer until the proposal is processed
+			T::Currency::reserve(&who, ProposalDeposit::<T>::get(dao_id))?;
+			let proposal = Proposal {
+				proposer: delegate.clone(),
+				applicant: delegate.clone(),
+				details: details,
+				content: content,
+				kind: ProposalKind::Parameters(parameters),
+				..Default::default()
+			};
+			let proposal_index = Self::enqueue_proposal(dao_id, proposal, &who)?;
+			Self::deposit_event(RawEvent::SubmitParametersProposal(dao_id, proposal_index, who, delegate));
+			Ok(())
+		}
 
-				// mint new shares
-				let totoal_shares = TotalShares::get().checked_add(proposal.shares_requested).unwrap();
-				TotalShares::put(totoal_shares);
-				// transfer correponding balance from custody account to guild bank's free balance
-				let res = T::Currency::transfer(&Self::custody_account(),  &Self::account_id(), token_tribute, AllowDeath);
-				debug::info!("asdsa---{:?}", res);
-				let custody_balance = ApplicantCustody::<T>::get(proposal.applicant.clone());
-				ApplicantCustody::<T>::insert(proposal.applicant.clone(), custody_balance - token_tribute);
-			} else {
-				// Proposal failed
-				// return the balance of applicant
-				let _ = T::Currency::transfer(&Self::custody_account(),  &proposal.applicant, token_tribute, AllowDeath);
-				let custody_balance = ApplicantCustody::<T>::get(proposal.applicant.clone());
-				ApplicantCustody::<T>::insert(proposal.applicant.clone(), custody_balance - token_tribute);
-			}
+		/// One of the members proposes a call the guild dispatches with its own origin once the proposal passes
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1,1)]
+		#[transactional]
+		pub fn submit_action_proposal(origin, dao_id: DaoId, action: Box<<T as Config>::Action>, details: Vec<u8>, content: Option<ProposalContentOf<T>>) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_dao(dao_id)?;
+			Self::ensure_details(&details, &content)?;
+			ensure!(AddressOfDelegates::<T>::contains_key(dao_id, who.clone()), Error::<T>::NotMember);
+			let delegate = AddressOfDelegates::<T>::get(dao_id, who.clone());
+			ensure!(Members::<T>::get(dao_id, delegate.clone()).shares > 0, Error::<T>::NoEnoughShares);
+			ensure!(action.get_dispatch_info().weight <= T::MaxActionWeight::get(), Error::<T>::ActionTooHeavy);
 
-			// need to mutate for update
-			ProposalQueue::<T>::mutate(|ps| {
-				ps[_usize_proposal_index] = proposal.clone();
-			});
+			// reserve the proposal deposit of the proposer until the proposal is processed
+			T::Currency::reserve(&who, ProposalDeposit::<T>::get(dao_id))?;
+			let proposal = Proposal {
+				proposer: delegate.clone(),
+				applicant: delegate.clone(),
+				details: details,
+				content: content,
+				kind: ProposalKind::Action(action.encode()),
+				..Default::default()
+			};
+			let proposal_index = Self::enqueue_proposal(dao_id, proposal, &who)?;
+			Self::deposit_event(RawEvent::SubmitActionProposal(dao_id, proposal_index, who, delegate));
+			Ok(())
+		}
 
-			// send reward
-			let _ = T::Currency::transfer(&Self::account_id(), &who, ProcessingReward::<T>::get(), KeepAlive);
-			// return deposit with reward slashed
-			let rest_balance = ProposalDeposit::<T>::get() - ProcessingReward::<T>::get();
-			let _ = T::Currency::transfer(&Self::account_id(), &proposal.proposer, rest_balance, KeepAlive);			
-
-			Self::deposit_event(RawEvent::ProcessProposal(
-				proposal_index, 
-				proposal.applicant.clone(),
-				proposal.proposer.clone(),
-				proposal.token_tribute,
-				proposal.shares_requested,
-				did_pass
+		/// Anyone applies to the guild, the tribute stays reserved until the proposal is cancelled or processed
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1,1)]
+		#[transactional]
+		pub fn propose(origin, dao_id: DaoId, #[compact] token_tribute: BalanceOf<T>, shares_requested: u128,
+			           loot_requested: u128, #[compact] payment_requested: BalanceOf<T>, details: Vec<u8>, content: Option<ProposalContentOf<T>>) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_dao(dao_id)?;
+			Self::ensure_details(&details, &content)?;
+			ensure!(!Members::<T>::get(dao_id, who.clone()).jailed, Error::<T>::MemberJailed);
+			let proposal_id = UnsponsoredProposalCount::get(dao_id);
+			let next_id = proposal_id.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
+			let token_tribute_num = Self::balance_to_u128(token_tribute)?;
+			let payment_requested_num = Self::balance_to_u128(payment_requested)?;
+			// locked by the proposal, not part of the proposer's custody
+			T::Currency::reserve(&who, token_tribute)?;
+			Self::hold_tribute(&who, token_tribute)?;
+			Self::lock_tribute(dao_id, &who, None, token_tribute_num)?;
+
+			let proposal = Proposal {
+				proposer: who.clone(),
+				applicant: who.clone(),
+				shares_requested: shares_requested,
+				loot_requested: loot_requested,
+				starting_period: 0,
+				yes_votes: 0,
+				no_votes: 0,
+				processed: false,
+				did_pass: false,
+				aborted: false,
+				token_tribute: token_tribute_num,
+				tribute_token: None,
+				payment_requested: payment_requested_num,
+				details: details,
+				content: content,
+				max_total_shares_at_yes: 0,
+				kind: ProposalKind::Membership,
+				// the deposit is recorded when the proposal gets in the queue
+				..Default::default()
+			};
+			UnsponsoredProposals::<T>::insert(dao_id, proposal_id, proposal);
+			UnsponsoredProposalCount::insert(dao_id, next_id);
+			Self::deposit_event(RawEvent::Propose(dao_id, proposal_id, who, token_tribute_num, shares_requested, loot_requested, payment_requested_num));
+			Ok(())
+		}
+
+		/// Same as `propose`, with the tribute paid in one of the tokens whitelisted by the guild
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1,1)]
+		#[transactional]
+		pub fn propose_with_token(origin, dao_id: DaoId, tribute_token: AssetIdOf<T>, #[compact] token_tribute: u128, shares_requested: u128,
+			                      loot_requested: u128, #[compact] payment_requested: BalanceOf<T>, details: Vec<u8>, content: Option<ProposalContentOf<T>>) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_dao(dao_id)?;
+			Self::ensure_details(&details, &content)?;
+			ensure!(!Members::<T>::get(dao_id, who.clone()).jailed, Error::<T>::MemberJailed);
+			ensure!(ApprovedTokens::<T>::get(dao_id).contains(&tribute_token), Error::<T>::TokenNotWhitelisted);
+			let proposal_id = UnsponsoredProposalCount::get(dao_id);
+			let next_id = proposal_id.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
+			let payment_requested_num = Self::balance_to_u128(payment_requested)?;
+			T::Assets::reserve(tribute_token, &who, token_tribute)?;
+			Self::lock_tribute(dao_id, &who, Some(tribute_token), token_tribute)?;
+
+			let proposal = Proposal {
+				proposer: who.clone(),
+				applicant: who.clone(),
+				shares_requested: shares_requested,
+				loot_requested: loot_requested,
+				token_tribute: token_tribute,
+				tribute_token: Some(tribute_token),
+				payment_requested: payment_requested_num,
+				details: details,
+				content: content,
+				kind: ProposalKind::Membership,
+				..Default::default()
+			};
+			UnsponsoredProposals::<T>::insert(dao_id, proposal_id, proposal);
+			UnsponsoredProposalCount::insert(dao_id, next_id);
+			Self::deposit_event(RawEvent::ProposeWithToken(
+				dao_id,
+				proposal_id,
+				who,
+				tribute_token,
+				token_tribute,
+				shares_requested,
+				loot_requested,
+				payment_requested_num
 			));
 			Ok(())
 		}
 
-		/// Member rage quit
+		/// One of the members sponsors a proposal, reserving the proposal deposit and putting it in the queue
 		#[weight = 10_000 + T::DbWeight::get().reads_writes(1,1)]
-		pub fn ragequit(origin, shares_to_burn: u128) -> dispatch::DispatchResult {
+		#[transactional]
+		pub fn sponsor_proposal(origin, dao_id: DaoId, proposal_id: u128) -> dispatch::DispatchResult {
 			let who = ensure_signed(origin)?;
-			ensure!(Members::<T>::contains_key(who.clone()), Error::<T>::NotMember);
-			let member = &mut Members::<T>::get(who.clone());
-			ensure!(member.shares >= shares_to_burn, Error::<T>::NoEnoughShares);
-			// check if can rage quit
-			let proposal_index = member.highest_index_yes_vote;
-			ensure!(proposal_index < ProposalQueue::<T>::get().len().try_into().unwrap(), Error::<T>::ProposalNotExist);
-			let _usize_proposal_index = TryInto::<usize>::try_into(proposal_index).ok().unwrap();
-			ensure!(ProposalQueue::<T>::get()[_usize_proposal_index].processed, Error::<T>::ProposalNotProcessed);
-
-			// burn shares
-			Members::<T>::mutate(who.clone(), |mem| {
-				mem.shares = member.shares.checked_sub(shares_to_burn).unwrap();
-			});
-			let initial_total = TotalShares::get();
-			let totoal_shares = initial_total.checked_sub(shares_to_burn).unwrap();
-			TotalShares::put(totoal_shares);
+			Self::ensure_dao(dao_id)?;
+			ensure!(AddressOfDelegates::<T>::contains_key(dao_id, who.clone()), Error::<T>::NotMember);
+			let delegate = AddressOfDelegates::<T>::get(dao_id, who.clone());
+			ensure!(Members::<T>::get(dao_id, delegate.clone()).shares > 0, Error::<T>::NoEnoughShares);
+			ensure!(UnsponsoredProposals::<T>::contains_key(dao_id, proposal_id), Error::<T>::ProposalNotExist);
+			let mut proposal = UnsponsoredProposals::<T>::get(dao_id, proposal_id);
+			let total_requested = TotalSharesRequested::get(dao_id).checked_add(proposal.shares_requested).ok_or(Error::<T>::SharesOverFlow)?;
+			let total_loot_requested = TotalLootRequested::get(dao_id).checked_add(proposal.loot_requested).ok_or(Error::<T>::SharesOverFlow)?;
+			// shares and loot share the same cap
+			let future_shares = TotalShares::get(dao_id).checked_add(total_requested)
+				.and_then(|shares| shares.checked_add(TotalLoot::get(dao_id)))
+				.and_then(|shares| shares.checked_add(total_loot_requested))
+				.ok_or(Error::<T>::SharesOverFlow)?;
+			ensure!(future_shares <= T::MaxShares::get(), Error::<T>::SharesOverFlow);
+			ensure!(!Members::<T>::get(dao_id, proposal.applicant.clone()).jailed, Error::<T>::MemberJailed);
 
-			// withdraw the tokens
-			let amount = Self::balance_to_u128(T::Currency::free_balance(&Self::account_id()));
-			let balance = amount.checked_mul(shares_to_burn).unwrap().checked_div(initial_total).unwrap();
-			let _ = T::Currency::transfer(&Self::account_id(), &who, Self::u128_to_balance(balance), KeepAlive);			
+			// reserve the proposal deposit of the sponsor until the proposal is processed
+			T::Currency::reserve(&who, ProposalDeposit::<T>::get(dao_id))?;
+			TotalSharesRequested::insert(dao_id, total_requested);
+			TotalLootRequested::insert(dao_id, total_loot_requested);
+			UnsponsoredProposals::<T>::remove(dao_id, proposal_id);
 
-			Self::deposit_event(RawEvent::Ragequit(who.clone(), shares_to_burn));
+			// the sponsor gets the deposit back once the proposal is processed
+			proposal.proposer = delegate.clone();
+			let proposal_index = Self::enqueue_proposal(dao_id, proposal, &who)?;
+			Self::deposit_event(RawEvent::SponsorProposal(dao_id, proposal_id, proposal_index, who, delegate));
 			Ok(())
 		}
 
-		/// Member rage quit
-		#[weight = 10_000 + T::DbWeight::get().reads_writes(1,1)]
-		pub fn abort(origin, proposal_index: u128) -> dispatch::DispatchResult {
+		/// The member who submitted or sponsored a proposal cancels it before voting starts,
+		/// the tribute and the whole deposit are returned, the proposal stays in queue until it expires
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(5,5)]
+		#[transactional]
+		pub fn cancel_queued_proposal(origin, dao_id: DaoId, proposal_index: u128) -> dispatch::DispatchResult {
 			let who = ensure_signed(origin)?;
-			ensure!(proposal_index < ProposalQueue::<T>::get().len().try_into().unwrap(), Error::<T>::ProposalNotExist);
-			
-			let _usize_proposal_index = TryInto::<usize>::try_into(proposal_index).ok().unwrap();
-			let proposal = &mut ProposalQueue::<T>::get()[_usize_proposal_index];
-			ensure!(who == proposal.applicant, Error::<T>::NotProposalApplicant);
-			ensure!(
-				Self::get_current_period() < proposal.starting_period.checked_add(AbortWindow::get()).unwrap(),
-				Error::<T>::AbortWindowHasPassed
-			);
+			Self::ensure_dao(dao_id)?;
+			ensure!(AddressOfDelegates::<T>::contains_key(dao_id, who.clone()), Error::<T>::NotMember);
+			let delegate = AddressOfDelegates::<T>::get(dao_id, who.clone());
+			ensure!(Proposals::<T>::contains_key(dao_id, proposal_index), Error::<T>::ProposalNotExist);
+			let proposal = &mut Proposals::<T>::get(dao_id, proposal_index);
+			ensure!(delegate == proposal.proposer, Error::<T>::NotProposalProposer);
+			ensure!(Self::get_current_period(dao_id)? < proposal.starting_period, Error::<T>::ProposalHasStarted);
 			ensure!(!proposal.aborted, Error::<T>::ProposalHasAborted);
-			let token_to_abort = proposal.token_tribute;
+			let deposit = Self::u128_to_balance(proposal.deposit)?;
+			let total_requested = TotalSharesRequested::get(dao_id).checked_sub(proposal.shares_requested).ok_or(Error::<T>::Underflow)?;
+			let total_loot_requested = TotalLootRequested::get(dao_id).checked_sub(proposal.loot_requested).ok_or(Error::<T>::Underflow)?;
+
+			Self::return_tribute(dao_id, proposal)?;
+			T::Currency::unreserve(&proposal.depositor, deposit);
+			// the requested shares and loot are released now, so expiring or processing the proposal releases nothing
+			TotalSharesRequested::insert(dao_id, total_requested);
+			TotalLootRequested::insert(dao_id, total_loot_requested);
+			proposal.shares_requested = 0;
+			proposal.loot_requested = 0;
 			proposal.token_tribute = 0;
+			proposal.deposit = 0;
 			proposal.aborted = true;
+			Proposals::<T>::insert(dao_id, proposal_index, proposal.clone());
+			Self::clear_stuck_proposal(dao_id, proposal_index);
+			Self::deposit_event(RawEvent::CancelQueuedProposal(dao_id, proposal_index, who));
+			Ok(())
+		}
 
-			// need to mutate for update
-			ProposalQueue::<T>::mutate(|ps| {
-				ps[_usize_proposal_index] = proposal.clone();
-			});
-
-			// return the token to applicant and delete record
-			let _ = T::Currency::transfer(&Self::custody_account(),  &proposal.applicant, Self::u128_to_balance(token_to_abort), AllowDeath);
-			ApplicantCustody::<T>::remove(&proposal.applicant);
-
-			Self::deposit_event(RawEvent::Abort(proposal_index, who.clone()));
+		/// Close up to `max` stale proposals at the head of the queue as failed, returning their tributes and whole deposits.
+		/// Stale proposals can not pass any more, so they do not wait for the grace period
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(3,5).saturating_mul(*max as Weight)]
+		#[transactional]
+		pub fn expire_proposals(origin, dao_id: DaoId, max: u32) -> dispatch::DispatchResult {
+			let _ = ensure_signed(origin)?;
+			Self::ensure_dao(dao_id)?;
+			let current_period = Self::get_current_period(dao_id)?;
+			let mut expired: u32 = 0;
+			while expired < max {
+				let proposal_index = ProcessedCount::get(dao_id);
+				if !Proposals::<T>::contains_key(dao_id, proposal_index) {
+					break;
+				}
+				let proposal = &mut Proposals::<T>::get(dao_id, proposal_index);
+				if !Self::is_stale(dao_id, proposal, current_period)? {
+					break;
+				}
+				Self::expire_proposal(dao_id, proposal_index, proposal)?;
+				expired += 1;
+			}
+			ensure!(expired > 0, Error::<T>::NoStaleProposal);
 			Ok(())
 		}
 
-		/// Member rage quit
+		/// The proposer cancels a proposal nobody sponsored yet and gets the tribute back
 		#[weight = 10_000 + T::DbWeight::get().reads_writes(1,1)]
-		pub fn update_delegate(origin, delegate_key: T::AccountId) -> dispatch::DispatchResult {
+		#[transactional]
+		pub fn cancel_proposal(origin, dao_id: DaoId, proposal_id: u128) -> dispatch::DispatchResult {
 			let who = ensure_signed(origin)?;
-			// skip checks if member is setting the delegate key to their member address
-			if who != delegate_key {
-				ensure!(!Members::<T>::contains_key(delegate_key.clone()), Error::<T>::NoOverwriteMember);
-				let delegate = AddressOfDelegates::<T>::get(delegate_key.clone());
-				ensure!(!Members::<T>::contains_key(delegate.clone()), Error::<T>::NoOverwriteDelegate);
-			}
+			Self::ensure_dao(dao_id)?;
+			ensure!(UnsponsoredProposals::<T>::contains_key(dao_id, proposal_id), Error::<T>::ProposalNotExist);
+			let proposal = UnsponsoredProposals::<T>::get(dao_id, proposal_id);
+			ensure!(who == proposal.proposer, Error::<T>::NotProposalProposer);
 
-			let member = &mut Members::<T>::get(who.clone());
-			AddressOfDelegates::<T>::remove(member.delegate_key.clone());
-			AddressOfDelegates::<T>::insert(delegate_key.clone(), who.clone());
-			member.delegate_key = delegate_key.clone();
-			Self::deposit_event(RawEvent::UpdateDelegateKey(who, delegate_key));
+			Self::return_tribute(dao_id, &proposal)?;
+			UnsponsoredProposals::<T>::remove(dao_id, proposal_id);
+			Self::deposit_event(RawEvent::CancelProposal(dao_id, proposal_id, who));
 			Ok(())
 		}
-	}
-}
 
-impl<T: Config> Module<T> {
-	// Add public immutables and private mutables.
+		/// One of the members submit a vote, or changes it while the voting period lasts,
+		/// the shares delegated to the member are tallied with the first vote
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(2 + 2 * T::MaxDelegators::get() as Weight, 2 + 2 * T::MaxDelegators::get() as Weight)]
+		pub fn submit_vote(origin, dao_id: DaoId, proposal_index: u128, vote_unit: u8) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_dao(dao_id)?;
+			ensure!(AddressOfDelegates::<T>::contains_key(dao_id, who.clone()), Error::<T>::NotMember);
+			let delegate = AddressOfDelegates::<T>::get(dao_id, who.clone());
+			let member = Members::<T>::get(dao_id, delegate.clone());
+			ensure!(member.shares > 0, Error::<T>::NoEnoughShares);
+			ensure!(!member.jailed, Error::<T>::MemberJailed);
+			
+			ensure!(Proposals::<T>::contains_key(dao_id, proposal_index), Error::<T>::ProposalNotExist);
+			let proposal = &mut Proposals::<T>::get(dao_id, proposal_index);
+			ensure!(vote_unit < 3 && vote_unit > 0, Error::<T>::InvalidVote);
+			let current_period = Self::get_current_period(dao_id)?;
+			ensure!(current_period >= proposal.starting_period, Error::<T>::ProposalNotStart);
+			let voting_ends = proposal.starting_period.checked_add(proposal.voting_period_length).ok_or(Error::<T>::Overflow)?;
+			ensure!(current_period < voting_ends, Error::<T>::ProposalExpired);
+			ensure!(!proposal.aborted, Error::<T>::ProposalHasAborted);
+			ensure!(!DelegatedVotes::<T>::contains_key((dao_id, proposal_index), delegate.clone()), Error::<T>::VoteDelegated);
+			// a member delegating after the delegate voted is not part of that ballot, so keeps their own vote on it
+			if let Some(to) = Self::active_delegation(dao_id, &delegate, current_period) {
+				ensure!(ProposalVotes::<T>::contains_key((dao_id, proposal_index), to), Error::<T>::VoteDelegated);
+			}
+			let vote = match vote_unit {
+				1 => Vote::Yes,
+				2 => Vote::No,
+				_ => Vote::Null
+			};
 
-	/// refer https://github.com/paritytech/substrate/blob/743accbe3256de2fc615adcaa3ab03ebdbbb4dbd/frame/treasury/src/lib.rs#L351
+			// ballots belong to the member, a new delegate key does not get a second vote
+			let previous = ProposalVotes::<T>::get((dao_id, proposal_index), delegate.clone());
+			ensure!(previous.vote != vote, Error::<T>::MemberHasVoted);
+			let ballot = if previous.vote == Vote::Null {
+				// the shares held when voting started count, not the ones acquired or rage quit since
+				let weight = Self::voting_shares(dao_id, &delegate, proposal.starting_period);
+				// tally the shares delegated to the member, unless the delegators got their say already
+				let mut ballot = Ballot { vote: vote, weight: weight, delegators: Vec::new() };
+				for delegator in Delegators::<T>::get(dao_id, delegate.clone()) {
+					let delegated = Self::active_delegation(dao_id, &delegator, current_period)
+						.map_or(false, |to| to == delegate);
+					if !delegated || ProposalVotes::<T>::contains_key((dao_id, proposal_index), delegator.clone()) ||
+						DelegatedVotes::<T>::contains_key((dao_id, proposal_index), delegator.clone()) {
+						continue;
+					}
+					if Members::<T>::get(dao_id, delegator.clone()).jailed {
+						continue;
+					}
+					let delegated_shares = Self::voting_shares(dao_id, &delegator, proposal.starting_period);
+					ballot.weight = ballot.weight.checked_add(delegated_shares).ok_or(Error::<T>::Overflow)?;
+					ballot.delegators.push(delegator);
+				}
+				ensure!(ballot.weight > 0, Error::<T>::NoEnoughShares);
+				ballot
+			} else {
+				// the weight stays the one tallied with the first vote
+				match previous.vote {
+					Vote::Yes => proposal.yes_votes = proposal.yes_votes.checked_sub(previous.weight).ok_or(Error::<T>::Underflow)?,
+					_ => proposal.no_votes = proposal.no_votes.checked_sub(previous.weight).ok_or(Error::<T>::Underflow)?,
+				}
+				Ballot { vote: vote, ..previous.clone() }
+			};
+			// update proposal
+			if vote == Vote::Yes {
+				proposal.yes_votes = proposal.yes_votes.checked_add(ballot.weight).ok_or(Error::<T>::Overflow)?;
+				// loot counts for the dilution bound, but not for the votes
+				let total_shares_and_loot = TotalShares::get(dao_id).checked_add(TotalLoot::get(dao_id)).ok_or(Error::<T>::Overflow)?;
+				if total_shares_and_loot > proposal.max_total_shares_at_yes {
+					proposal.max_total_shares_at_yes = total_shares_and_loot;
+				}
+				// the delegators can not ragequit before the proposal is processed either
+				for voter in sp_std::iter::once(&delegate).chain(ballot.delegators.iter()) {
+					Members::<T>::mutate(dao_id, voter, |mem| {
+						if mem.highest_index_yes_vote.map_or(true, |index| proposal_index > index) {
+							mem.highest_index_yes_vote = Some(proposal_index);
+						}
+					});
+				}
+			} else if vote == Vote::No {
+				proposal.no_votes = proposal.no_votes.checked_add(ballot.weight).ok_or(Error::<T>::Overflow)?;
+			}
+			if previous.vote == Vote::Null {
+				for delegator in &ballot.delegators {
+					DelegatedVotes::<T>::insert((dao_id, proposal_index), delegator, delegate.clone());
+				}
+			}
+			ProposalVotes::<T>::insert((dao_id, proposal_index), delegate.clone(), ballot);
+			// need to insert for update
+			Proposals::<T>::insert(dao_id, proposal_index, proposal.clone());
+			if previous.vote == Vote::Null {
+				Self::deposit_event(RawEvent::SubmitVote(dao_id, proposal_index, who, delegate, vote_unit));
+			} else {
+				Self::deposit_event(RawEvent::VoteChanged(dao_id, proposal_index, who, delegate, vote_unit));
+			}
+			Ok(())
+		}
+
+		/// Process a proposal in queue, the weight covers the call of an action proposal
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1,1) + T::MaxActionWeight::get()]
+		#[transactional]
+		pub fn process_proposal(origin, dao_id: DaoId, proposal_index: u128) -> dispatch::DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let action_weight = Self::do_process_proposal(dao_id, proposal_index, Some(&who))?;
+			// refund the part of `MaxActionWeight` the action did not use
+			Ok(Some(10_000 + T::DbWeight::get().reads_writes(1,1) + action_weight).into())
+		}
+
+		/// Process at most `max` proposals in queue up to `up_to_index` in one call, of which at most `max_actions` action proposals,
+		/// stopping at the first one not ready yet or failing. The processing reward is paid for each of them.
+		/// The weight covers `max` proposals and `max_actions` actions, the unused part is refunded
+		#[weight = 10_000 + (10_000 + T::DbWeight::get().reads_writes(2,1)).saturating_mul(*max as Weight)
+			.saturating_add(T::MaxActionWeight::get().saturating_mul(*max_actions as Weight))]
+		#[transactional]
+		pub fn process_proposals(origin, dao_id: DaoId, up_to_index: u128, max: u32, max_actions: u32) -> dispatch::DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::ensure_dao(dao_id)?;
+			ensure!(ProcessedCount::get(dao_id) <= up_to_index, Error::<T>::ProposalHasProcessed);
+			let mut weight: Weight = 10_000;
+			let mut processed: u32 = 0;
+			let mut actions: u32 = 0;
+			while processed < max && ProcessedCount::get(dao_id) <= up_to_index {
+				let proposal_index = match Self::next_ready_proposal(dao_id) {
+					Some(index) => index,
+					None => break,
+				};
+				let is_action = matches!(Proposals::<T>::get(dao_id, proposal_index).kind, ProposalKind::Action(_));
+				if is_action && actions >= max_actions {
+					break;
+				}
+				let result = with_transaction(|| match Self::do_process_proposal(dao_id, proposal_index, Some(&who)) {
+					Ok(action_weight) => TransactionOutcome::Commit(Ok(action_weight)),
+					Err(e) => TransactionOutcome::Rollback(Err(e)),
+				});
+				let action_weight = match result {
+					Ok(action_weight) => action_weight,
+					// the proposals processed so far are kept, the failing one is left to `process_proposal`
+					Err(e) if processed == 0 => return Err(e.into()),
+					Err(_) => break,
+				};
+				weight = weight.saturating_add(10_000 + T::DbWeight::get().reads_writes(2,1) + action_weight);
+				processed += 1;
+				if is_action {
+					actions += 1;
+				}
+			}
+			ensure!(processed > 0, Error::<T>::ProposalNotReady);
+			Ok(Some(weight).into())
+		}
+
+		/// Process the next proposal in queue without a signer, submitted by the off-chain worker,
+		/// nobody earns the processing reward
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1,1) + T::MaxActionWeight::get()]
+		#[transactional]
+		pub fn process_proposal_unsigned(origin, dao_id: DaoId, proposal_index: u128) -> dispatch::DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+			let action_weight = Self::do_process_proposal(dao_id, proposal_index, None)?;
+			Ok(Some(10_000 + T::DbWeight::get().reads_writes(1,1) + action_weight).into())
+		}
+
+		/// Member rage quit
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1,1)]
+		#[transactional]
+		pub fn ragequit(origin, dao_id: DaoId, shares_to_burn: u128, loot_to_burn: u128) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_dao(dao_id)?;
+			ensure!(Members::<T>::contains_key(dao_id, who.clone()), Error::<T>::NotMember);
+			let member = &mut Members::<T>::get(dao_id, who.clone());
+			ensure!(member.shares >= shares_to_burn, Error::<T>::NoEnoughShares);
+			ensure!(member.loot >= loot_to_burn, Error::<T>::NoEnoughLoot);
+			let shares_and_loot_to_burn = shares_to_burn.checked_add(loot_to_burn).ok_or(Error::<T>::Overflow)?;
+			ensure!(shares_and_loot_to_burn > 0, Error::<T>::NoEnoughShares);
+			// check if can rage quit, a member who never voted YES can at any time
+			if let Some(proposal_index) = member.highest_index_yes_vote {
+				ensure!(Proposals::<T>::contains_key(dao_id, proposal_index), Error::<T>::ProposalNotExist);
+				ensure!(Proposals::<T>::get(dao_id, proposal_index).processed, Error::<T>::ProposalNotProcessed);
+			}
+
+			// loot has the same claim on the guild bank as shares
+			let initial_total = TotalShares::get(dao_id).checked_add(TotalLoot::get(dao_id)).ok_or(Error::<T>::Overflow)?;
+			let totoal_shares = TotalShares::get(dao_id).checked_sub(shares_to_burn).ok_or(Error::<T>::Underflow)?;
+			let total_loot = TotalLoot::get(dao_id).checked_sub(loot_to_burn).ok_or(Error::<T>::Underflow)?;
+			let amount = Self::balance_to_u128(Self::distributable_balance(dao_id))?;
+			let balance = Self::u128_to_balance(Self::share_of(amount, shares_and_loot_to_burn, initial_total)?)?;
+
+			// burn shares and loot
+			Members::<T>::mutate(dao_id, who.clone(), |mem| {
+				mem.shares = member.shares.saturating_sub(shares_to_burn);
+				mem.loot = member.loot.saturating_sub(loot_to_burn);
+			});
+			TotalShares::insert(dao_id, totoal_shares);
+			TotalLoot::insert(dao_id, total_loot);
+			Self::checkpoint_shares(dao_id, &who)?;
+
+			// withdraw the tokens
+			T::Currency::transfer(&Self::account_id(dao_id), &who, balance, KeepAlive)?;
+			Self::withdraw_tokens(dao_id, &who, shares_and_loot_to_burn, initial_total)?;
+
+			Self::deposit_event(RawEvent::Ragequit(dao_id, who.clone(), shares_to_burn, loot_to_burn));
+			Ok(())
+		}
+
+		/// Anyone can withdraw the slice of the guild bank owed to a kicked member, it goes to the member
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1,1)]
+		#[transactional]
+		pub fn ragekick(origin, dao_id: DaoId, member_to_kick: T::AccountId) -> dispatch::DispatchResult {
+			let _ = ensure_signed(origin)?;
+			Self::ensure_dao(dao_id)?;
+			ensure!(Members::<T>::contains_key(dao_id, member_to_kick.clone()), Error::<T>::NotMember);
+			let member = Members::<T>::get(dao_id, member_to_kick.clone());
+			ensure!(member.jailed, Error::<T>::MemberNotJailed);
+			ensure!(member.loot > 0, Error::<T>::NoEnoughLoot);
+			// same as rage quit, wait for the proposals the member voted YES on
+			if let Some(proposal_index) = member.highest_index_yes_vote {
+				ensure!(Proposals::<T>::contains_key(dao_id, proposal_index), Error::<T>::ProposalNotExist);
+				ensure!(Proposals::<T>::get(dao_id, proposal_index).processed, Error::<T>::ProposalNotProcessed);
+			}
+
+			let loot_to_burn = member.loot;
+			let initial_total = TotalShares::get(dao_id).checked_add(TotalLoot::get(dao_id)).ok_or(Error::<T>::Overflow)?;
+			let total_loot = TotalLoot::get(dao_id).checked_sub(loot_to_burn).ok_or(Error::<T>::Underflow)?;
+			let amount = Self::balance_to_u128(Self::distributable_balance(dao_id))?;
+			let balance = Self::u128_to_balance(Self::share_of(amount, loot_to_burn, initial_total)?)?;
+
+			// burn loot
+			Members::<T>::mutate(dao_id, member_to_kick.clone(), |mem| {
+				mem.loot = 0;
+			});
+			TotalLoot::insert(dao_id, total_loot);
+
+			// withdraw the tokens
+			T::Currency::transfer(&Self::account_id(dao_id), &member_to_kick, balance, KeepAlive)?;
+			Self::withdraw_tokens(dao_id, &member_to_kick, loot_to_burn, initial_total)?;
+
+			Self::deposit_event(RawEvent::Ragekick(dao_id, member_to_kick, loot_to_burn));
+			Ok(())
+		}
+
+		/// Member rage quit
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1,1)]
+		#[transactional]
+		pub fn abort(origin, dao_id: DaoId, proposal_index: u128) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_dao(dao_id)?;
+			ensure!(Proposals::<T>::contains_key(dao_id, proposal_index), Error::<T>::ProposalNotExist);
+			let proposal = &mut Proposals::<T>::get(dao_id, proposal_index);
+			ensure!(proposal.kind == ProposalKind::Membership, Error::<T>::ProposalNotAbortable);
+			ensure!(who == proposal.applicant, Error::<T>::NotProposalApplicant);
+			let abort_window_ends = proposal.starting_period.checked_add(AbortWindow::get(dao_id)).ok_or(Error::<T>::Overflow)?;
+			ensure!(Self::get_current_period(dao_id)? < abort_window_ends, Error::<T>::AbortWindowHasPassed);
+			ensure!(!proposal.aborted, Error::<T>::ProposalHasAborted);
+			// return the token to applicant and delete record
+			Self::return_tribute(dao_id, proposal)?;
+			proposal.token_tribute = 0;
+			proposal.aborted = true;
+
+			// need to insert for update
+			Proposals::<T>::insert(dao_id, proposal_index, proposal.clone());
+			Self::clear_stuck_proposal(dao_id, proposal_index);
+
+			Self::deposit_event(RawEvent::Abort(dao_id, proposal_index, who.clone()));
+			Ok(())
+		}
+
+		/// Member lets `delegate_key` submit proposals and vote in their name, instead of their member address
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(3,3)]
+		pub fn update_delegate(origin, dao_id: DaoId, delegate_key: T::AccountId) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_dao(dao_id)?;
+			ensure!(Members::<T>::contains_key(dao_id, who.clone()), Error::<T>::NotMember);
+			// skip checks if member is setting the delegate key to their member address
+			if who != delegate_key {
+				ensure!(!Members::<T>::contains_key(dao_id, delegate_key.clone()), Error::<T>::NoOverwriteMember);
+				ensure!(!AddressOfDelegates::<T>::contains_key(dao_id, delegate_key.clone()), Error::<T>::NoOverwriteDelegate);
+			}
+
+			Self::set_delegate_key(dao_id, &who, &delegate_key);
+			Self::deposit_event(RawEvent::UpdateDelegateKey(dao_id, who, delegate_key));
+			Ok(())
+		}
+
+		/// The member, or their delegate stepping down, hands the delegate key back to the member address
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(3,3)]
+		pub fn revoke_delegate(origin, dao_id: DaoId) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_dao(dao_id)?;
+			let member = if Members::<T>::contains_key(dao_id, who.clone()) {
+				who
+			} else {
+				ensure!(AddressOfDelegates::<T>::contains_key(dao_id, who.clone()), Error::<T>::NotMember);
+				AddressOfDelegates::<T>::get(dao_id, who)
+			};
+			let delegate_key = Members::<T>::get(dao_id, member.clone()).delegate_key;
+			ensure!(delegate_key != member, Error::<T>::NoDelegateKey);
+
+			Self::set_delegate_key(dao_id, &member, &member);
+			Self::deposit_event(RawEvent::DelegateKeyRevoked(dao_id, member, delegate_key));
+			Ok(())
+		}
+
+		/// Member lets another member vote with their shares for `periods` periods, replacing any earlier delegation
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(5 + T::MaxDelegators::get() as Weight, 3)]
+		pub fn delegate_vote(origin, dao_id: DaoId, to: T::AccountId, periods: u128) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_dao(dao_id)?;
+			ensure!(AddressOfDelegates::<T>::contains_key(dao_id, who.clone()), Error::<T>::NotMember);
+			let delegator = AddressOfDelegates::<T>::get(dao_id, who.clone());
+			ensure!(Members::<T>::get(dao_id, delegator.clone()).shares > 0, Error::<T>::NoEnoughShares);
+			let current_period = Self::get_current_period(dao_id)?;
+			// delegations are not transitive, the shares would never get tallied
+			let target = Members::<T>::get(dao_id, to.clone());
+			ensure!(
+				to != delegator && target.shares > 0 && !target.jailed &&
+				Self::active_delegation(dao_id, &to, current_period).is_none(),
+				Error::<T>::InvalidDelegate
+			);
+			// nor can a delegate pass on the shares delegated to them
+			ensure!(
+				!Delegators::<T>::get(dao_id, delegator.clone()).iter()
+					.any(|d| Self::active_delegation(dao_id, d, current_period).is_some()),
+				Error::<T>::InvalidDelegate
+			);
+			ensure!(periods > 0, Error::<T>::InvalidDelegate);
+			let until = current_period.checked_add(periods).ok_or(Error::<T>::Overflow)?;
+			let mut delegators = Delegators::<T>::get(dao_id, to.clone());
+			// the expired delegations do not take up room
+			delegators.retain(|d| Self::active_delegation(dao_id, d, current_period).map_or(false, |d_to| d_to == to));
+			if !delegators.contains(&delegator) {
+				ensure!((delegators.len() as u32) < T::MaxDelegators::get(), Error::<T>::TooManyDelegators);
+				delegators.push(delegator.clone());
+			}
+
+			Self::remove_delegation(dao_id, &delegator);
+			Delegators::<T>::insert(dao_id, to.clone(), delegators);
+			VoteDelegations::<T>::insert(dao_id, delegator.clone(), (to.clone(), until));
+			Self::deposit_event(RawEvent::VoteDelegated(dao_id, delegator, to, until));
+			Ok(())
+		}
+
+		/// Member takes back their voting weight, the votes already cast by the delegate stay as they are
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(3, 2)]
+		pub fn undelegate_vote(origin, dao_id: DaoId) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_dao(dao_id)?;
+			ensure!(AddressOfDelegates::<T>::contains_key(dao_id, who.clone()), Error::<T>::NotMember);
+			let delegator = AddressOfDelegates::<T>::get(dao_id, who.clone());
+			ensure!(VoteDelegations::<T>::contains_key(dao_id, delegator.clone()), Error::<T>::InvalidDelegate);
+			Self::remove_delegation(dao_id, &delegator);
+			Self::deposit_event(RawEvent::VoteUndelegated(dao_id, delegator));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Module<T> {
+	// Add public immutables and private mutables.
+
+	/// refer https://github.com/paritytech/substrate/blob/743accbe3256de2fc615adcaa3ab03ebdbbb4dbd/frame/treasury/src/lib.rs#L351
 	///
 	/// This actually does computation. If you need to keep using it, then make sure you cache the
 	/// value and only call this once.
-	pub fn account_id() -> T::AccountId {
-		T::ModuleId::get().into_account()
+	pub fn account_id(dao_id: DaoId) -> T::AccountId {
+		T::ModuleId::get().into_sub_account(GuildAccount::GuildBank(dao_id))
 	}
 
-	pub fn custody_account() -> T::AccountId {
+	/// Escrow of the tributes of the single guild before `Releases::V4`, they are reserved on the applicant now
+	fn legacy_custody_account() -> T::AccountId {
 		T::ModuleId::get().into_sub_account("custody")
 	}
 
-	pub fn u128_to_balance(cost: u128) -> BalanceOf<T> {
-		TryInto::<BalanceOf::<T>>::try_into(cost).ok().unwrap()
+	/// Make sure the guild has been summoned
+	pub fn ensure_dao(dao_id: DaoId) -> dispatch::DispatchResult {
+		ensure!(dao_id < DaoCount::get(), Error::<T>::DaoNotExist);
+		Ok(())
 	}
 
-	pub fn balance_to_u128(balance: BalanceOf<T>) -> u128 {
-		TryInto::<u128>::try_into(balance).ok().unwrap()
+	pub fn u128_to_balance(cost: u128) -> Result<BalanceOf<T>, DispatchError> {
+		TryInto::<BalanceOf::<T>>::try_into(cost).map_err(|_| Error::<T>::BalanceConversion.into())
 	}
 
-	pub fn get_current_period() -> u128 {
-		let now = TryInto::<u128>::try_into(pallet_timestamp::Module::<T>::now()).ok().unwrap();
-		let summon_time = TryInto::<u128>::try_into(SummonTime::<T>::get()).ok().unwrap();
-		let diff = now.checked_sub(summon_time).unwrap();
-		// the timestamp is in milli seconds
-		diff.checked_div(1000).unwrap().checked_div(PeriodDuration::get().into()).unwrap()
+	pub fn balance_to_u128(balance: BalanceOf<T>) -> Result<u128, DispatchError> {
+		TryInto::<u128>::try_into(balance).map_err(|_| Error::<T>::BalanceConversion.into())
+	}
+
+	/// The slice `units / total_units` of `amount`, the product of `amount` and `units` may exceed `u128`
+	fn share_of(amount: u128, units: u128, total_units: u128) -> Result<u128, DispatchError> {
+		ensure!(total_units > 0, Error::<T>::NoEnoughShares);
+		multiply_by_rational(amount, units, total_units).map_err(|_| Error::<T>::Overflow.into())
+	}
+
+	/// Free balance of the guild bank
+	pub fn guild_bank_balance(dao_id: DaoId) -> BalanceOf<T> {
+		T::Currency::free_balance(&Self::account_id(dao_id))
+	}
+
+	/// Part of the guild bank owned by the shares and loot, without the existential deposit keeping the bank alive.
+	/// Deposits, tributes and unused custody stay reserved on their owners, so they are never part of it
+	pub fn distributable_balance(dao_id: DaoId) -> BalanceOf<T> {
+		Self::guild_bank_balance(dao_id).saturating_sub(T::Currency::minimum_balance())
+	}
+
+	/// Balance of the guild bank in one of the whitelisted tokens
+	pub fn guild_bank_token_balance(dao_id: DaoId, token: AssetIdOf<T>) -> u128 {
+		T::Assets::balance(token, &Self::account_id(dao_id))
+	}
+
+	// Below are the queries exposed through the runtime api
+
+	pub fn current_period(dao_id: DaoId) -> Option<u128> {
+		if Self::ensure_dao(dao_id).is_err() {
+			return None;
+		}
+		Self::get_current_period(dao_id).ok()
+	}
+
+	// (shares, loot, highest_index_yes_vote, delegate_key)
+	pub fn member_info(dao_id: DaoId, who: T::AccountId) -> Option<(u128, u128, Option<u128>, T::AccountId)> {
+		if !Members::<T>::contains_key(dao_id, &who) {
+			return None;
+		}
+		let member = Members::<T>::get(dao_id, &who);
+		Some((member.shares, member.loot, member.highest_index_yes_vote, member.delegate_key))
 	}
 
-}
\ No newline at end of file
+	pub fn proposal_status(dao_id: DaoId, proposal_index: u128) -> Option<ProposalStatus> {
+		if !Proposals::<T>::contains_key(dao_id, proposal_index) {
+			return None;
+		}
+		let proposal = Proposals::<T>::get(dao_id, proposal_index);
+		if proposal.processed {
+			return Some(ProposalStatus::Processed);
+		}
+		// every proposal is pending until the clock reaches the summon time
+		let current_period = Self::get_current_period(dao_id).unwrap_or(0);
+		let voting_ends = proposal.starting_period.saturating_add(proposal.voting_period_length);
+		let grace_ends = voting_ends.saturating_add(proposal.grace_period_length);
+		let status = if current_period < proposal.starting_period {
+			ProposalStatus::Pending
+		} else if current_period < voting_ends {
+			ProposalStatus::Voting
+		} else if current_period < grace_ends {
+			ProposalStatus::Grace
+		} else {
+			ProposalStatus::Ready
+		};
+		Some(status)
+	}
+
+	/// Hash and uri of the body a proposal in queue keeps off-chain
+	pub fn proposal_content(dao_id: DaoId, proposal_index: u128) -> Option<ProposalContentOf<T>> {
+		if !Proposals::<T>::contains_key(dao_id, proposal_index) {
+			return None;
+		}
+		Proposals::<T>::get(dao_id, proposal_index).content
+	}
+
+	/// Tokens a member would get back from the guild bank by burning `shares_to_burn`, loot is worth as much as shares
+	pub fn ragequit_preview(dao_id: DaoId, shares_to_burn: u128) -> BalanceOf<T> {
+		let total_shares = TotalShares::get(dao_id).saturating_add(TotalLoot::get(dao_id));
+		if total_shares == 0 || shares_to_burn > total_shares {
+			return Zero::zero();
+		}
+		Self::balance_to_u128(Self::distributable_balance(dao_id)).ok()
+			.and_then(|amount| Self::share_of(amount, shares_to_burn, total_shares).ok())
+			.and_then(|balance| Self::u128_to_balance(balance).ok())
+			.unwrap_or_else(Zero::zero)
+	}
+
+	/// Form a new guild, `funder` pays the existential deposit of the guild bank and the funding,
+	/// which are minted when there is no funder at genesis
+	fn do_summon(
+		funder: Option<&T::AccountId>,
+		period_duration: u32,
+		parameters: &GuildParameters,
+		founders: &[(T::AccountId, u128, u128)],
+		funding: BalanceOf<T>,
+	) -> Result<DaoId, DispatchError> {
+		ensure!(period_duration > 0, Error::<T>::InvalidPeriodDuration);
+		Self::ensure_parameters(parameters)?;
+		let mut total_shares: u128 = 0;
+		let mut total_loot: u128 = 0;
+		for (index, (founder, shares, loot)) in founders.iter().enumerate() {
+			ensure!(!founders[..index].iter().any(|(f, _, _)| f == founder), Error::<T>::NoOverwriteMember);
+			total_shares = total_shares.checked_add(*shares).ok_or(Error::<T>::SharesOverFlow)?;
+			total_loot = total_loot.checked_add(*loot).ok_or(Error::<T>::SharesOverFlow)?;
+		}
+		// someone has to be able to vote
+		ensure!(total_shares > 0, Error::<T>::NoEnoughShares);
+		ensure!(
+			total_shares.checked_add(total_loot).map_or(false, |total| total <= T::MaxShares::get()),
+			Error::<T>::SharesOverFlow
+		);
+
+		let dao_id = DaoCount::get();
+		let next_id = dao_id.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
+		// the guild bank gets the existential deposit so it stays alive
+		let bank_balance = funding.saturating_add(T::Currency::minimum_balance());
+		match funder {
+			Some(who) => T::Currency::transfer(who, &Self::account_id(dao_id), bank_balance, KeepAlive)?,
+			None => {
+				let _ = T::Currency::deposit_creating(&Self::account_id(dao_id), bank_balance);
+			},
+		}
+		DaoCount::put(next_id);
+
+		SummonTime::insert(dao_id, T::PeriodClock::now());
+		PeriodDuration::insert(dao_id, period_duration);
+		Self::set_parameters(dao_id, parameters)?;
+		for (founder, shares, loot) in founders {
+			let member = Member {
+				shares: *shares,
+				highest_index_yes_vote: None,
+				exists: true,
+				delegate_key: founder.clone(),
+				loot: *loot,
+				jailed: false,
+			};
+			Members::<T>::insert(dao_id, founder.clone(), member);
+			AddressOfDelegates::<T>::insert(dao_id, founder.clone(), founder.clone());
+		}
+		TotalShares::insert(dao_id, total_shares);
+		TotalLoot::insert(dao_id, total_loot);
+		for (founder, _, _) in founders {
+			Self::checkpoint_shares(dao_id, founder)?;
+		}
+		Ok(dao_id)
+	}
+
+	/// Check the details and the content uri of a proposal against `MaxDetailsLength` and their encoding
+	fn ensure_details(details: &[u8], content: &Option<ProposalContentOf<T>>) -> dispatch::DispatchResult {
+		let max_length = T::MaxDetailsLength::get() as usize;
+		ensure!(details.len() <= max_length, Error::<T>::DetailsTooLong);
+		ensure!(sp_std::str::from_utf8(details).is_ok(), Error::<T>::InvalidDetails);
+		if let Some(content) = content {
+			ensure!(content.uri.len() <= max_length, Error::<T>::DetailsTooLong);
+			ensure!(!content.uri.is_empty() && content.uri.iter().all(u8::is_ascii_graphic), Error::<T>::InvalidDetails);
+		}
+		Ok(())
+	}
+
+	/// Check the settings of a guild against the bounds of `Config`
+	fn ensure_parameters(parameters: &GuildParameters) -> dispatch::DispatchResult {
+		ensure!(parameters.voting_period_length <= T::MaxVotingPeriodLength::get(), Error::<T>::VotingPeriodLengthTooBig);
+		ensure!(parameters.grace_period_length <= T::MaxGracePeriodLength::get(), Error::<T>::GracePeriodLengthTooBig);
+		ensure!(parameters.dilution_bound <= T::MaxDilutionBound::get(), Error::<T>::DilutionBoundTooBig);
+		ensure!(parameters.abort_window <= parameters.voting_period_length, Error::<T>::AbortWindowTooBig);
+		ensure!(parameters.proposal_deposit >= parameters.processing_reward, Error::<T>::NoEnoughProposalDeposit);
+		Self::u128_to_balance(parameters.proposal_deposit)?;
+		for (index, (category, threshold)) in parameters.thresholds.iter().enumerate() {
+			// a pass threshold of 100 could never be beaten
+			ensure!(threshold.quorum <= 100 && threshold.pass < 100, Error::<T>::InvalidThreshold);
+			ensure!(parameters.thresholds[..index].iter().all(|(other, _)| other != category), Error::<T>::InvalidThreshold);
+		}
+		Ok(())
+	}
+
+	fn set_parameters(dao_id: DaoId, parameters: &GuildParameters) -> dispatch::DispatchResult {
+		let proposal_deposit = Self::u128_to_balance(parameters.proposal_deposit)?;
+		let processing_reward = Self::u128_to_balance(parameters.processing_reward)?;
+		VotingPeriodLength::insert(dao_id, parameters.voting_period_length);
+		GracePeriodLength::insert(dao_id, parameters.grace_period_length);
+		AbortWindow::insert(dao_id, parameters.abort_window);
+		DilutionBound::insert(dao_id, parameters.dilution_bound);
+		ProposalDeposit::<T>::insert(dao_id, proposal_deposit);
+		ProcessingReward::<T>::insert(dao_id, processing_reward);
+		Thresholds::remove_prefix(dao_id);
+		for (category, threshold) in &parameters.thresholds {
+			Thresholds::insert(dao_id, category, threshold);
+		}
+		Ok(())
+	}
+
+	/// Process the proposal at `proposal_index`, `processor` gets the processing reward,
+	/// returns the weight of the call of an action proposal
+	fn do_process_proposal(dao_id: DaoId, proposal_index: u128, processor: Option<&T::AccountId>) -> Result<Weight, DispatchError> {
+		Self::ensure_dao(dao_id)?;
+		ensure!(Proposals::<T>::contains_key(dao_id, proposal_index), Error::<T>::ProposalNotExist);
+		let proposal = &mut Proposals::<T>::get(dao_id, proposal_index);
+		let ready_period = proposal.starting_period
+			.checked_add(proposal.voting_period_length)
+			.and_then(|period| period.checked_add(proposal.grace_period_length))
+			.ok_or(Error::<T>::Overflow)?;
+		ensure!(Self::get_current_period(dao_id)? >= ready_period, Error::<T>::ProposalNotReady);
+		ensure!(proposal.processed == false, Error::<T>::ProposalHasProcessed);
+		// proposals are processed in order, everything before the cursor has been processed
+		ensure!(proposal_index == ProcessedCount::get(dao_id), Error::<T>::PreviousProposalNotProcessed);
+
+		// do all the fallible arithmetic before touching the storage
+		let processed_count = proposal_index.checked_add(1).ok_or(Error::<T>::Overflow)?;
+		let total_requested = TotalSharesRequested::get(dao_id).checked_sub(proposal.shares_requested).ok_or(Error::<T>::Underflow)?;
+		let total_loot_requested = TotalLootRequested::get(dao_id).checked_sub(proposal.loot_requested).ok_or(Error::<T>::Underflow)?;
+		let total_shares_and_loot = TotalShares::get(dao_id).checked_add(TotalLoot::get(dao_id)).ok_or(Error::<T>::Overflow)?;
+		let totoal_shares = TotalShares::get(dao_id).checked_add(proposal.shares_requested).ok_or(Error::<T>::Overflow)?;
+		let total_loot = TotalLoot::get(dao_id).checked_add(proposal.loot_requested).ok_or(Error::<T>::Overflow)?;
+		let token_tribute = Self::u128_to_balance(proposal.token_tribute)?;
+		let payment_requested = Self::u128_to_balance(proposal.payment_requested)?;
+		// the guild may have changed its settings since the deposit was paid
+		let proposal_deposit = Self::u128_to_balance(proposal.deposit)?;
+		// nobody earns the reward when the chain processes the proposal by itself
+		let processing_reward = match processor {
+			Some(_) => ProcessingReward::<T>::get(dao_id).min(proposal_deposit),
+			None => Zero::zero(),
+		};
+		// the votes needed depend on the kind of the proposal
+		let threshold = Thresholds::get(dao_id, proposal.kind.category());
+		let votes = proposal.yes_votes.checked_add(proposal.no_votes).ok_or(Error::<T>::Overflow)?;
+		// the votes were weighted with the shares held when voting started, so is the quorum
+		let total_shares_at_start = Self::shares_at(&TotalShareCheckpoints::get(dao_id), proposal.starting_period);
+		let quorum_reached = votes.checked_mul(100).ok_or(Error::<T>::Overflow)? >=
+			total_shares_at_start.checked_mul(threshold.quorum.into()).ok_or(Error::<T>::Overflow)?;
+		let threshold_reached = proposal.yes_votes.checked_mul(100).ok_or(Error::<T>::Overflow)? >
+			votes.checked_mul(threshold.pass.into()).ok_or(Error::<T>::Overflow)?;
+
+		let mut action_weight: Weight = 0;
+		proposal.processed = true;
+		ProcessedCount::insert(dao_id, processed_count);
+		Self::clear_stuck_proposal(dao_id, proposal_index);
+		TotalSharesRequested::insert(dao_id, total_requested);
+		TotalLootRequested::insert(dao_id, total_loot_requested);
+		let mut did_pass = quorum_reached && threshold_reached;
+
+		// as anyone can process the proposal and get rewarded, so do not fail here
+		if total_shares_and_loot.saturating_mul(DilutionBound::get(dao_id)) < proposal.max_total_shares_at_yes {
+			Self::deposit_event(RawEvent::DilutionBoundExeceeds(dao_id, total_shares_and_loot, DilutionBound::get(dao_id), proposal.max_total_shares_at_yes));
+			did_pass = false;
+		}
+
+		// an applicant kicked in the meantime can not get shares, and a member can only be kicked once
+		if Members::<T>::get(dao_id, &proposal.applicant).jailed {
+			did_pass = false;
+		}
+
+		// the tribute locked by the proposal may have been slashed in the meantime, the reserve of the applicant
+		// has to cover everything the guilds hold for them, not only this proposal
+		let mut custody_outage = false;
+		if proposal.kind == ProposalKind::Membership {
+			match proposal.tribute_token {
+				None => {
+					let custody_balance = T::Currency::reserved_balance(&proposal.applicant);
+					let held = HeldTributes::<T>::get(&proposal.applicant);
+					if custody_balance < held {
+						Self::deposit_event(RawEvent::CustodyBalanceOutage(dao_id, custody_balance, held));
+						custody_outage = true;
+					}
+				},
+				Some(token) => {
+					let custody_balance = T::Assets::reserved_balance(token, &proposal.applicant);
+					let locked = LockedTributes::<T>::get((dao_id, Some(token)), &proposal.applicant);
+					if custody_balance < locked {
+						Self::deposit_event(RawEvent::TokenCustodyOutage(dao_id, token, custody_balance, locked));
+						custody_outage = true;
+					}
+				},
+			}
+		}
+		did_pass = did_pass && !custody_outage;
+
+		// the payment must be covered by the guild bank, which has to stay alive
+		if proposal.payment_requested > 0 {
+			let available = Self::distributable_balance(dao_id);
+			if available < payment_requested {
+				Self::deposit_event(RawEvent::GuildBankBalanceOutage(dao_id, available, payment_requested));
+				did_pass = false;
+			}
+		}
+
+		if proposal.kind == ProposalKind::GuildKick {
+			// nothing was escrowed for a guild kick, so there is nothing to return if it failed
+			if did_pass {
+				proposal.did_pass = true;
+				Self::kick_member(dao_id, &proposal.applicant)?;
+			}
+		} else if let ProposalKind::Whitelist(token) = proposal.kind {
+			// the token may have been whitelisted or the whitelist filled up since the proposal was submitted
+			let mut approved_tokens = ApprovedTokens::<T>::get(dao_id);
+			did_pass = did_pass && !approved_tokens.contains(&token) &&
+				(approved_tokens.len() as u32) < T::MaxWhitelistedTokens::get();
+			if did_pass {
+				proposal.did_pass = true;
+				approved_tokens.push(token);
+				ApprovedTokens::<T>::insert(dao_id, approved_tokens);
+				Self::deposit_event(RawEvent::TokenWhitelisted(dao_id, token));
+			}
+		} else if let ProposalKind::Parameters(parameters) = proposal.kind.clone() {
+			// the bounds of `Config` may have changed with a runtime upgrade
+			did_pass = did_pass && Self::ensure_parameters(&parameters).is_ok();
+			if did_pass {
+				proposal.did_pass = true;
+				Self::set_parameters(dao_id, &parameters)?;
+				Self::deposit_event(RawEvent::ParametersUpdated(dao_id, proposal_index));
+			}
+		} else if let ProposalKind::Action(encoded_action) = proposal.kind.clone() {
+			if did_pass {
+				proposal.did_pass = true;
+				// the runtime may have been upgraded since the call got encoded
+				let result: dispatch::DispatchResult = match <T as Config>::Action::decode(&mut &encoded_action[..]) {
+					// the weight of the call may have grown past the bound checked at submission
+					Ok(action) if action.get_dispatch_info().weight > T::MaxActionWeight::get() => Err(Error::<T>::ActionTooHeavy.into()),
+					Ok(action) => {
+						action_weight = action.get_dispatch_info().weight;
+						// a failed call leaves nothing behind but the event
+						with_transaction(|| match action.dispatch(RawOrigin::Dao(dao_id).into()) {
+							Ok(_) => TransactionOutcome::Commit(Ok(())),
+							Err(e) => TransactionOutcome::Rollback(Err(e.error)),
+						})
+					},
+					Err(_) => Err(Error::<T>::InvalidAction.into()),
+				};
+				Self::deposit_event(RawEvent::ActionExecuted(dao_id, proposal_index, result));
+			}
+		} else if did_pass && !proposal.aborted {
+			// Proposal passed
+			proposal.did_pass = true;
+
+			// if the applicant is already a member, add to their existing shares
+			if Members::<T>::contains_key(dao_id, &proposal.applicant) {
+				Members::<T>::mutate(dao_id, &proposal.applicant, |mem| {
+					// bounded by the totals checked above
+					mem.shares = mem.shares.saturating_add(proposal.shares_requested);
+					mem.loot = mem.loot.saturating_add(proposal.loot_requested);
+				});
+			} else {
+				// if the applicant address is already taken by a member's delegateKey, reset it to their member address
+				if AddressOfDelegates::<T>::contains_key(dao_id, proposal.applicant.clone()) {
+					let delegator = AddressOfDelegates::<T>::get(dao_id, proposal.applicant.clone());
+					Self::set_delegate_key(dao_id, &delegator, &delegator);
+				}
+				// add new member
+				let member = Member {
+					shares: proposal.shares_requested,
+					highest_index_yes_vote: None,
+					exists: true,
+					delegate_key: proposal.applicant.clone(),
+					loot: proposal.loot_requested,
+					jailed: false,
+				};
+				Members::<T>::insert(dao_id, proposal.applicant.clone(), member);
+				AddressOfDelegates::<T>::insert(dao_id, proposal.applicant.clone(), proposal.applicant.clone());
+			}
+
+			// mint new shares and loot
+			TotalShares::insert(dao_id, totoal_shares);
+			TotalLoot::insert(dao_id, total_loot);
+			Self::checkpoint_shares(dao_id, &proposal.applicant)?;
+			// move the reserved tribute to guild bank's free balance,
+			// both custody and the guild bank have been checked above, a failure reverts the processing
+			Self::accept_tribute(dao_id, proposal)?;
+			if proposal.payment_requested > 0 {
+				T::Currency::transfer(&Self::account_id(dao_id), &proposal.applicant, payment_requested, KeepAlive)?;
+				Self::deposit_event(RawEvent::PaymentTransferred(dao_id, proposal_index, proposal.applicant.clone(), payment_requested));
+			}
+		} else {
+			// Proposal failed
+			// return the balance of applicant, whatever is left of it
+			Self::return_tribute(dao_id, proposal)?;
+		}
+
+		// need to insert for update
+		Proposals::<T>::insert(dao_id, proposal_index, proposal.clone());
+
+		// send reward out of the deposit
+		if let Some(who) = processor {
+			T::Currency::repatriate_reserved(&proposal.depositor, who, processing_reward, BalanceStatus::Free)?;
+		}
+		// return deposit with reward slashed
+		let rest_balance = proposal_deposit.saturating_sub(processing_reward);
+		T::Currency::unreserve(&proposal.depositor, rest_balance);
+
+		Self::deposit_event(RawEvent::ProcessProposal(
+			dao_id,
+			proposal_index, 
+			proposal.applicant.clone(),
+			proposal.proposer.clone(),
+			proposal.token_tribute,
+			proposal.shares_requested,
+			proposal.loot_requested,
+			did_pass,
+			quorum_reached,
+			threshold_reached
+		));
+		Ok(action_weight)
+	}
+
+	/// A proposal is stale once it is cancelled or aborted, or once voting ended without a yes vote
+	fn is_stale(dao_id: DaoId, proposal: &ProposalOf<T>, current_period: u128) -> Result<bool, DispatchError> {
+		if proposal.aborted {
+			return Ok(true);
+		}
+		let voting_ends = proposal.starting_period.checked_add(proposal.voting_period_length).ok_or(Error::<T>::Overflow)?;
+		Ok(current_period >= voting_ends && proposal.yes_votes == 0)
+	}
+
+	/// Close the proposal at the head of the queue as failed, nobody earns the processing reward
+	fn expire_proposal(dao_id: DaoId, proposal_index: u128, proposal: &mut ProposalOf<T>) -> dispatch::DispatchResult {
+		let processed_count = proposal_index.checked_add(1).ok_or(Error::<T>::Overflow)?;
+		let total_requested = TotalSharesRequested::get(dao_id).checked_sub(proposal.shares_requested).ok_or(Error::<T>::Underflow)?;
+		let total_loot_requested = TotalLootRequested::get(dao_id).checked_sub(proposal.loot_requested).ok_or(Error::<T>::Underflow)?;
+		let deposit = Self::u128_to_balance(proposal.deposit)?;
+
+		proposal.processed = true;
+		ProcessedCount::insert(dao_id, processed_count);
+		Self::clear_stuck_proposal(dao_id, proposal_index);
+		TotalSharesRequested::insert(dao_id, total_requested);
+		TotalLootRequested::insert(dao_id, total_loot_requested);
+		Self::return_tribute(dao_id, proposal)?;
+		T::Currency::unreserve(&proposal.depositor, deposit);
+		Proposals::<T>::insert(dao_id, proposal_index, proposal.clone());
+		Self::deposit_event(RawEvent::ProposalExpired(dao_id, proposal_index));
+		Ok(())
+	}
+
+	/// Point the delegate key of `member` at `delegate_key`, keeping `AddressOfDelegates` the inverse of `Member::delegate_key`
+	fn set_delegate_key(dao_id: DaoId, member: &T::AccountId, delegate_key: &T::AccountId) {
+		let old_key = Members::<T>::get(dao_id, member).delegate_key;
+		if AddressOfDelegates::<T>::get(dao_id, &old_key) == *member {
+			AddressOfDelegates::<T>::remove(dao_id, &old_key);
+		}
+		Members::<T>::mutate(dao_id, member, |mem| mem.delegate_key = delegate_key.clone());
+		AddressOfDelegates::<T>::insert(dao_id, delegate_key, member);
+	}
+
+	/// Member the vote of `delegator` is delegated to in `period`
+	fn active_delegation(dao_id: DaoId, delegator: &T::AccountId, period: u128) -> Option<T::AccountId> {
+		VoteDelegations::<T>::get(dao_id, delegator)
+			.filter(|(_, until)| period < *until)
+			.map(|(to, _)| to)
+	}
+
+	fn remove_delegation(dao_id: DaoId, delegator: &T::AccountId) {
+		if let Some((to, _)) = VoteDelegations::<T>::take(dao_id, delegator) {
+			Delegators::<T>::mutate(dao_id, to, |delegators| delegators.retain(|d| d != delegator));
+		}
+	}
+
+	/// Index of the next proposal in queue, once it is ready to be processed
+	fn next_ready_proposal(dao_id: DaoId) -> Option<u128> {
+		let proposal_index = ProcessedCount::get(dao_id);
+		if !Proposals::<T>::contains_key(dao_id, proposal_index) {
+			return None;
+		}
+		let proposal = Proposals::<T>::get(dao_id, proposal_index);
+		let ready_period = proposal.starting_period
+			.checked_add(proposal.voting_period_length)?
+			.checked_add(proposal.grace_period_length)?;
+		if proposal.processed || Self::get_current_period(dao_id).ok()? < ready_period {
+			return None;
+		}
+		Some(proposal_index)
+	}
+
+	/// The next ready proposal, unless `on_initialize` failed to process it and left it to `process_proposal`
+	fn next_unattended_proposal(dao_id: DaoId) -> Option<u128> {
+		Self::next_ready_proposal(dao_id).filter(|index| StuckProposal::get(dao_id) != Some(*index))
+	}
+
+	/// The proposal has been processed or changed, `on_initialize` may look at the guild's queue again
+	fn clear_stuck_proposal(dao_id: DaoId, proposal_index: u128) {
+		if StuckProposal::get(dao_id) == Some(proposal_index) {
+			StuckProposal::remove(dao_id);
+		}
+	}
+
+	/// Process the ready proposals of the guilds within `limit`, starting with the guild the previous block stopped at
+	fn auto_process(limit: Weight) -> Weight {
+		let dao_count = DaoCount::get();
+		let mut weight = T::DbWeight::get().reads(2);
+		if limit == 0 || dao_count == 0 {
+			return weight;
+		}
+		// the clock settings, the queue cursor, the next proposal and the stuck marker of the guild
+		let per_guild = T::DbWeight::get().reads(6);
+		let per_proposal = 10_000 + T::DbWeight::get().reads_writes(1,1) + T::MaxActionWeight::get();
+		let mut dao_id = AutoProcessCursor::get() % dao_count;
+		for _ in 0..dao_count {
+			if weight + per_guild + per_proposal > limit {
+				break;
+			}
+			weight += per_guild;
+			while weight + per_proposal <= limit {
+				let proposal_index = match Self::next_unattended_proposal(dao_id) {
+					Some(index) => index,
+					None => break,
+				};
+				weight += per_proposal;
+				let result = with_transaction(|| match Self::do_process_proposal(dao_id, proposal_index, None) {
+					Ok(_) => TransactionOutcome::Commit(Ok(())),
+					Err(e) => TransactionOutcome::Rollback(Err(e)),
+				});
+				if let Err(e) = result {
+					// left to `process_proposal`, the hook would only fail the same way again
+					StuckProposal::insert(dao_id, proposal_index);
+					Self::deposit_event(RawEvent::AutoProcessFailed(dao_id, proposal_index, e));
+					break;
+				}
+			}
+			dao_id = (dao_id + 1) % dao_count;
+		}
+		AutoProcessCursor::put(dao_id);
+		weight + T::DbWeight::get().writes(1)
+	}
+
+	/// Count the tribute of a new proposal in `LockedTributes`
+	fn lock_tribute(dao_id: DaoId, applicant: &T::AccountId, tribute_token: Option<AssetIdOf<T>>, amount: u128) -> dispatch::DispatchResult {
+		if amount == 0 {
+			return Ok(());
+		}
+		let locked = LockedTributes::<T>::get((dao_id, tribute_token), applicant)
+			.checked_add(amount)
+			.ok_or(Error::<T>::StorageOverflow)?;
+		LockedTributes::<T>::insert((dao_id, tribute_token), applicant, locked);
+		Ok(())
+	}
+
+	/// The proposal releases its tribute, to the guild bank or back to the applicant
+	fn unlock_tribute(dao_id: DaoId, proposal: &ProposalOf<T>) {
+		let key = (dao_id, proposal.tribute_token);
+		let locked = LockedTributes::<T>::get(key, &proposal.applicant).saturating_sub(proposal.token_tribute);
+		if locked == 0 {
+			LockedTributes::<T>::remove(key, &proposal.applicant);
+		} else {
+			LockedTributes::<T>::insert(key, &proposal.applicant, locked);
+		}
+		if proposal.tribute_token.is_none() {
+			Self::release_tribute(&proposal.applicant, proposal.token_tribute.saturated_into());
+		}
+	}
+
+	/// Count `Config::Currency` just reserved on `who` as custody or tribute in `HeldTributes`
+	fn hold_tribute(who: &T::AccountId, amount: BalanceOf<T>) -> dispatch::DispatchResult {
+		let held = HeldTributes::<T>::get(who).checked_add(&amount).ok_or(Error::<T>::StorageOverflow)?;
+		HeldTributes::<T>::insert(who, held);
+		Ok(())
+	}
+
+	/// Stop counting custody or tribute in `HeldTributes` once it leaves the reserve of `who`
+	fn release_tribute(who: &T::AccountId, amount: BalanceOf<T>) {
+		let held = HeldTributes::<T>::get(who).saturating_sub(amount);
+		if held.is_zero() {
+			HeldTributes::<T>::remove(who);
+		} else {
+			HeldTributes::<T>::insert(who, held);
+		}
+	}
+
+	/// Move the tribute reserved by the applicant of a passed proposal to the guild bank
+	fn accept_tribute(dao_id: DaoId, proposal: &ProposalOf<T>) -> dispatch::DispatchResult {
+		let guild_bank = Self::account_id(dao_id);
+		Self::unlock_tribute(dao_id, proposal);
+		match proposal.tribute_token {
+			Some(token) => T::Assets::repatriate_reserved(token, &proposal.applicant, &guild_bank, proposal.token_tribute),
+			None => {
+				let token_tribute = Self::u128_to_balance(proposal.token_tribute)?;
+				let missing = T::Currency::repatriate_reserved(&proposal.applicant, &guild_bank, token_tribute, BalanceStatus::Free)?;
+				ensure!(missing.is_zero(), Error::<T>::TributeNotReserved);
+				Ok(())
+			},
+		}
+	}
+
+	/// Unreserve the tribute locked by a proposal which got cancelled, aborted or did not pass
+	fn return_tribute(dao_id: DaoId, proposal: &ProposalOf<T>) -> dispatch::DispatchResult {
+		Self::unlock_tribute(dao_id, proposal);
+		match proposal.tribute_token {
+			Some(token) => {
+				let reserved = T::Assets::reserved_balance(token, &proposal.applicant);
+				T::Assets::unreserve(token, &proposal.applicant, proposal.token_tribute.min(reserved))
+			},
+			None => {
+				let token_tribute = Self::u128_to_balance(proposal.token_tribute)?;
+				T::Currency::unreserve(&proposal.applicant, token_tribute);
+				Ok(())
+			},
+		}
+	}
+
+	/// Pay `who` the slice `units / total_units` of every whitelisted token in the guild bank
+	fn withdraw_tokens(dao_id: DaoId, who: &T::AccountId, units: u128, total_units: u128) -> dispatch::DispatchResult {
+		if total_units == 0 {
+			return Ok(());
+		}
+		let guild_bank = Self::account_id(dao_id);
+		for token in ApprovedTokens::<T>::get(dao_id) {
+			let amount = Self::share_of(T::Assets::balance(token, &guild_bank), units, total_units)?;
+			if amount > 0 {
+				T::Assets::transfer(token, &guild_bank, who, amount)?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Turn all the shares of a kicked member into loot and jail them
+	fn kick_member(dao_id: DaoId, who: &T::AccountId) -> dispatch::DispatchResult {
+		let shares = Members::<T>::get(dao_id, who).shares;
+		Members::<T>::mutate(dao_id, who, |mem| {
+			mem.loot = mem.loot.saturating_add(mem.shares);
+			mem.shares = 0;
+			mem.jailed = true;
+		});
+		TotalShares::mutate(dao_id, |total| *total = total.saturating_sub(shares));
+		TotalLoot::mutate(dao_id, |total| *total = total.saturating_add(shares));
+		Self::checkpoint_shares(dao_id, who)
+	}
+
+	/// Record the shares of `who` and the total shares after they changed in the current period,
+	/// dropping the history no proposal in the queue can ask for any more
+	fn checkpoint_shares(dao_id: DaoId, who: &T::AccountId) -> dispatch::DispatchResult {
+		let period = Self::get_current_period(dao_id)?;
+		// proposals start in queue order, the next one to process starts the earliest
+		let processed_count = ProcessedCount::get(dao_id);
+		let oldest_start = if processed_count < ProposalCount::get(dao_id) {
+			Proposals::<T>::get(dao_id, processed_count).starting_period
+		} else {
+			period.saturating_add(1)
+		};
+		let shares = Members::<T>::get(dao_id, who).shares;
+		ShareCheckpoints::<T>::mutate(dao_id, who, |checkpoints| {
+			Self::push_checkpoint(checkpoints, period, shares, oldest_start)
+		});
+		let total_shares = TotalShares::get(dao_id);
+		TotalShareCheckpoints::mutate(dao_id, |checkpoints| {
+			Self::push_checkpoint(checkpoints, period, total_shares, oldest_start)
+		});
+		Ok(())
+	}
+
+	fn push_checkpoint(checkpoints: &mut Vec<(u128, u128)>, period: u128, shares: u128, oldest_start: u128) {
+		match checkpoints.last_mut() {
+			Some(last) if last.0 == period => last.1 = shares,
+			_ => checkpoints.push((period, shares)),
+		}
+		// only the last checkpoint before the oldest start can still be looked up
+		if let Some(first) = checkpoints.iter().rposition(|(p, _)| *p < oldest_start) {
+			checkpoints.drain(..first);
+		}
+	}
+
+	/// Shares recorded in `checkpoints` when voting on a proposal starting in `period` began
+	fn shares_at(checkpoints: &[(u128, u128)], period: u128) -> u128 {
+		checkpoints.iter().rev().find(|(p, _)| *p < period).map_or(0, |(_, shares)| *shares)
+	}
+
+	/// Shares a member votes with on a proposal starting at `period`, the shares rage quit since do not count
+	fn voting_shares(dao_id: DaoId, who: &T::AccountId, period: u128) -> u128 {
+		Self::shares_at(&ShareCheckpoints::<T>::get(dao_id, who), period).min(Members::<T>::get(dao_id, who).shares)
+	}
+
+	/// Put a sponsored proposal at the end of the queue, voting starts the period after the previous proposal's
+	fn enqueue_proposal(dao_id: DaoId, mut proposal: ProposalOf<T>, depositor: &T::AccountId) -> Result<u128, DispatchError> {
+		let proposal_index = ProposalCount::get(dao_id);
+		let next_index = proposal_index.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
+		let proposal_period = match proposal_index {
+			0 => 0,
+			n => Proposals::<T>::get(dao_id, n-1).starting_period
+		};
+		proposal.starting_period = proposal_period.max(Self::get_current_period(dao_id)?).checked_add(1).ok_or(Error::<T>::Overflow)?;
+		// the deposit has just been reserved on the sponsor
+		proposal.deposit = Self::balance_to_u128(ProposalDeposit::<T>::get(dao_id))?;
+		// a parameters proposal passing later does not move the periods members voted with
+		proposal.voting_period_length = VotingPeriodLength::get(dao_id);
+		proposal.grace_period_length = GracePeriodLength::get(dao_id);
+		proposal.depositor = depositor.clone();
+		Proposals::<T>::insert(dao_id, proposal_index, proposal);
+		ProposalCount::insert(dao_id, next_index);
+		Ok(proposal_index)
+	}
+
+	/// Move the single guild of `Releases::V1` into `DaoId` 0, along with the balance of its bank,
+	/// `SummonTime`, `ProposalQueue` and the custody account are left to the steps converting them
+	fn migrate_to_multiple_guilds() -> Weight {
+		let dao_id: DaoId = 0;
+		let mut reads: Weight = 1;
+		let mut writes: Weight = 0;
+		// the guild got its period duration when it was summoned
+		if let Some(period_duration) = take_storage_value::<u32>(PALLET_PREFIX, b"PeriodDuration", &[]) {
+			let take_u128 = |item: &[u8]| take_storage_value::<u128>(PALLET_PREFIX, item, &[]).unwrap_or_default();
+			let take_balance = |item: &[u8]| take_storage_value::<BalanceOf<T>>(PALLET_PREFIX, item, &[]).unwrap_or_default();
+			DaoCount::put(dao_id + 1);
+			PeriodDuration::insert(dao_id, period_duration);
+			TotalShares::insert(dao_id, take_u128(b"TotalShares"));
+			TotalSharesRequested::insert(dao_id, take_u128(b"TotalSharesRequested"));
+			VotingPeriodLength::insert(dao_id, take_u128(b"VotingPeriodLength"));
+			GracePeriodLength::insert(dao_id, take_u128(b"GracePeriodLength"));
+			AbortWindow::insert(dao_id, take_u128(b"AbortWindow"));
+			DilutionBound::insert(dao_id, take_u128(b"DilutionBound"));
+			ProposalDeposit::<T>::insert(dao_id, take_balance(b"ProposalDeposit"));
+			ProcessingReward::<T>::insert(dao_id, take_balance(b"ProcessingReward"));
+			reads += 8;
+			writes += 18;
+		}
+
+		for (delegate_key, who) in Self::drain_legacy_map::<T::AccountId, T::AccountId>(b"AddressOfDelegates") {
+			AddressOfDelegates::<T>::insert(dao_id, delegate_key, who);
+			reads += 1;
+			writes += 2;
+		}
+		for (applicant, tribute) in Self::drain_legacy_map::<T::AccountId, BalanceOf<T>>(b"ApplicantCustody") {
+			ApplicantCustody::<T>::insert(dao_id, applicant, tribute);
+			reads += 1;
+			writes += 2;
+		}
+		// the raw vote units are turned into ballots by `migrate_votes_to_ballots`
+		let votes = StorageIterator::<u8>::new(PALLET_PREFIX, b"ProposalVotes").drain().collect::<Vec<_>>();
+		for (key, vote_unit) in votes {
+			reads += 1;
+			writes += 1;
+			// `blake2_128_concat` of the proposal index, then of the voter
+			let mut input = key.get(16..).unwrap_or_default();
+			let decoded = u128::decode(&mut input).ok()
+				.and_then(|index| input.get(16..).map(|voter| (index, voter)))
+				.and_then(|(index, mut voter)| T::AccountId::decode(&mut voter).ok().map(|voter| (index, voter)));
+			match decoded {
+				Some((proposal_index, voter)) => {
+					unhashed::put(&ProposalVotes::<T>::hashed_key_for((dao_id, proposal_index), voter), &vote_unit);
+					writes += 1;
+				},
+				None => debug::error!("moloch: dropped the vote stored under the undecodable key {:?}", key),
+			}
+		}
+		for (who, member) in Self::drain_legacy_map::<T::AccountId, LegacyMember<T::AccountId>>(b"Members") {
+			// the index 0 is also what a member who never voted YES holds, the rekeyed votes tell them apart
+			let highest_index_yes_vote = match member.highest_index_yes_vote {
+				0 => unhashed::get::<u8>(&ProposalVotes::<T>::hashed_key_for((dao_id, 0), &who))
+					.filter(|vote_unit| *vote_unit == 1)
+					.map(|_| 0),
+				index => Some(index),
+			};
+			let member = Member {
+				shares: member.shares,
+				highest_index_yes_vote: highest_index_yes_vote,
+				exists: member.exists,
+				delegate_key: member.delegate_key,
+				loot: 0,
+				jailed: false,
+			};
+			Members::<T>::insert(dao_id, who, member);
+			reads += 2;
+			writes += 2;
+		}
+
+		let legacy_bank: T::AccountId = T::ModuleId::get().into_account();
+		let balance = T::Currency::free_balance(&legacy_bank);
+		reads += 1;
+		if !balance.is_zero() {
+			writes += 2;
+			if T::Currency::transfer(&legacy_bank, &Self::account_id(dao_id), balance, AllowDeath).is_err() {
+				debug::error!("moloch: failed to move {:?} from the guild bank {:?} to guild 0", balance, legacy_bank);
+			}
+		}
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	/// Drain a `blake2_128_concat` map of the single guild layout, a value which does not decode is left in place
+	fn drain_legacy_map<K: Decode, V: Decode>(item: &[u8]) -> Vec<(K, V)> {
+		let entries = StorageIterator::<V>::new(PALLET_PREFIX, item).drain().collect::<Vec<_>>();
+		entries.into_iter()
+			.filter_map(|(key, value)| {
+				let decoded = key.get(16..).and_then(|mut input| K::decode(&mut input).ok());
+				if decoded.is_none() {
+					debug::error!("moloch: dropped the entry of {:?} stored under the undecodable key {:?}", item, key);
+				}
+				decoded.map(|key| (key, value))
+			})
+			.collect()
+	}
+
+	/// Index the proposals of the single `ProposalQueue` vector of `Releases::V1` in `Proposals`,
+	/// a queue which does not decode is left in place for a later fix rather than dropped
+	fn migrate_to_indexed_proposals() -> Weight {
+		let dao_id: DaoId = 0;
+		let mut reads: Weight = 2;
+		let mut writes: Weight = 1;
+		let key = [Twox128::hash(PALLET_PREFIX), Twox128::hash(b"ProposalQueue")].concat();
+		if let Some(raw_queue) = unhashed::get_raw(&key) {
+			match Vec::<LegacyProposal<T::AccountId>>::decode(&mut &raw_queue[..]) {
+				Ok(queue) => {
+					let proposal_count = queue.len() as u128;
+					// everything before the first unprocessed proposal has been processed
+					let processed_count = queue.iter().position(|p| !p.processed).unwrap_or(queue.len()) as u128;
+					// the sponsor paid the deposit of the guild into the guild bank, it is still held for the unprocessed proposals
+					let deposit = Self::balance_to_u128(ProposalDeposit::<T>::get(dao_id)).unwrap_or_default();
+					for (index, proposal) in queue.into_iter().enumerate() {
+						let proposal = Proposal {
+							depositor: proposal.proposer.clone(),
+							deposit: if proposal.processed { 0 } else { deposit },
+							proposer: proposal.proposer,
+							applicant: proposal.applicant,
+							shares_requested: proposal.shares_requested,
+							loot_requested: 0,
+							starting_period: proposal.starting_period,
+							yes_votes: proposal.yes_votes,
+							no_votes: proposal.no_votes,
+							processed: proposal.processed,
+							did_pass: proposal.did_pass,
+							aborted: proposal.aborted,
+							token_tribute: proposal.token_tribute,
+							tribute_token: None,
+							payment_requested: 0,
+							details: proposal.details,
+							content: None,
+							max_total_shares_at_yes: proposal.max_total_shares_at_yes,
+							kind: ProposalKind::Membership,
+							voting_period_length: VotingPeriodLength::get(dao_id),
+							grace_period_length: GracePeriodLength::get(dao_id),
+						};
+						Proposals::<T>::insert(dao_id, index as u128, proposal);
+					}
+					ProposalCount::insert(dao_id, proposal_count);
+					ProcessedCount::insert(dao_id, processed_count);
+					unhashed::kill(&key);
+					writes += 3 + proposal_count as Weight;
+				},
+				Err(_) => debug::error!("moloch: the proposal queue does not decode, it is left in place and no proposal got indexed"),
+			}
+		}
+		StorageVersion::put(Releases::V2);
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	/// The single `SummonTime` of `Releases::V1` is the timestamp in milli seconds the guild was summoned at,
+	/// and its `PeriodDuration` counts seconds, convert both to the unit of `Config::PeriodClock`
+	fn migrate_summon_time() -> Weight {
+		let dao_id: DaoId = 0;
+		let mut writes: Weight = 1;
+		if let Some(summon_time) = take_storage_value::<T::Moment>(PALLET_PREFIX, b"SummonTime", &[]) {
+			let summon_time = summon_time.saturated_into::<u128>();
+			let now = pallet_timestamp::Module::<T>::now().saturated_into::<u128>();
+			let period_duration = (PeriodDuration::get(dao_id) as u128).saturating_mul(1000);
+			let period_duration = T::PeriodClock::at_timestamp(now.saturating_add(period_duration))
+				.saturating_sub(T::PeriodClock::at_timestamp(now))
+				// a period can not be shorter than one tick of the clock
+				.max(1);
+			SummonTime::insert(dao_id, T::PeriodClock::at_timestamp(summon_time));
+			PeriodDuration::insert(dao_id, period_duration.saturated_into::<u32>());
+			writes += 3;
+		}
+		StorageVersion::put(Releases::V3);
+		T::DbWeight::get().reads_writes(4, writes)
+	}
+
+	/// Hand the tributes escrowed in the custody account of `Releases::V1` back to the applicants, reserved,
+	/// and reserve the deposits of the queued proposals on their sponsors instead of holding them in the guild bank
+	fn migrate_custody_to_reserves() -> Weight {
+		let legacy_custody = Self::legacy_custody_account();
+		let mut reads: Weight = 1;
+		let mut writes: Weight = 1;
+		for (dao_id, applicant, tribute) in ApplicantCustody::<T>::iter() {
+			reads += 1;
+			writes += 3;
+			// the runtime had no assets pallet so only `Config::Currency` was escrowed
+			if T::Currency::transfer(&legacy_custody, &applicant, tribute, AllowDeath).is_err() {
+				// left in custody, the proposal will fail on a custody outage
+				debug::error!("moloch: failed to return the custody {:?} of {:?}", tribute, applicant);
+				continue;
+			}
+			if T::Currency::reserve(&applicant, tribute).is_err() {
+				// the tribute is free again, nothing is held for the applicant any more
+				ApplicantCustody::<T>::remove(dao_id, &applicant);
+			} else if Self::hold_tribute(&applicant, tribute).is_err() {
+				debug::error!("moloch: failed to count the custody {:?} of {:?}", tribute, applicant);
+			}
+		}
+
+		for dao_id in 0..DaoCount::get() {
+			let guild_bank = Self::account_id(dao_id);
+			for proposal_index in ProcessedCount::get(dao_id)..ProposalCount::get(dao_id) {
+				let mut proposal = Proposals::<T>::get(dao_id, proposal_index);
+				reads += 2;
+				if proposal.processed {
+					continue;
+				}
+				// the custody of the applicant used to count the tribute of their queued proposals as well
+				let tribute = Self::u128_to_balance(proposal.token_tribute).unwrap_or_default();
+				let custody = ApplicantCustody::<T>::get(dao_id, &proposal.applicant).saturating_sub(tribute);
+				if custody.is_zero() {
+					ApplicantCustody::<T>::remove(dao_id, &proposal.applicant);
+				} else {
+					ApplicantCustody::<T>::insert(dao_id, &proposal.applicant, custody);
+				}
+				if Self::lock_tribute(dao_id, &proposal.applicant, proposal.tribute_token, proposal.token_tribute).is_err() {
+					debug::error!("moloch: failed to lock the tribute of proposal {} of guild {}", proposal_index, dao_id);
+				}
+				writes += 2;
+
+				let deposit = Self::u128_to_balance(proposal.deposit).unwrap_or_default();
+				if deposit.is_zero() {
+					continue;
+				}
+				let reserved = T::Currency::transfer(&guild_bank, &proposal.depositor, deposit, KeepAlive)
+					.and_then(|_| T::Currency::reserve(&proposal.depositor, deposit));
+				if reserved.is_err() {
+					// nothing is held for the sponsor, processing the proposal neither returns nor rewards a deposit
+					debug::error!("moloch: failed to reserve the deposit of proposal {} of guild {}", proposal_index, dao_id);
+					proposal.deposit = 0;
+					Proposals::<T>::insert(dao_id, proposal_index, proposal);
+				}
+				writes += 3;
+			}
+		}
+		StorageVersion::put(Releases::V4);
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	/// Turn the raw vote units into ballots, weighted with the current shares of the voters
+	fn migrate_votes_to_ballots() -> Weight {
+		let count = Cell::new(0 as Weight);
+		ProposalVotes::<T>::translate(|(dao_id, _), voter: T::AccountId, vote_unit: u8| {
+			count.set(count.get() + 1);
+			let vote = match vote_unit {
+				1 => Vote::Yes,
+				2 => Vote::No,
+				_ => return None,
+			};
+			let weight = Members::<T>::get(dao_id, voter).shares;
+			Some(Ballot { vote: vote, weight: weight, delegators: Vec::new() })
+		});
+		StorageVersion::put(Releases::V5);
+		let count = count.get();
+		T::DbWeight::get().reads_writes(2 * count + 1, count + 1)
+	}
+
+	/// Start the share history with the current shares, taken as held since the first period
+	fn migrate_share_checkpoints() -> Weight {
+		let mut count: Weight = 0;
+		for (dao_id, who, member) in Members::<T>::iter() {
+			ShareCheckpoints::<T>::insert(dao_id, who, sp_std::vec![(0, member.shares)]);
+			count += 1;
+		}
+		let dao_count = DaoCount::get();
+		for dao_id in 0..dao_count {
+			TotalShareCheckpoints::insert(dao_id, sp_std::vec![(0, TotalShares::get(dao_id))]);
+		}
+		let dao_count = dao_count as Weight;
+		StorageVersion::put(Releases::V6);
+		T::DbWeight::get().reads_writes(count + dao_count + 2, count + dao_count + 1)
+	}
+
+	pub fn get_current_period(dao_id: DaoId) -> Result<u128, DispatchError> {
+		let now = T::PeriodClock::now();
+		let summon_time = SummonTime::get(dao_id);
+		let diff = now.checked_sub(summon_time).ok_or(Error::<T>::PeriodNotReached)?;
+		diff.checked_div(PeriodDuration::get(dao_id).into()).ok_or(Error::<T>::InvalidPeriodDuration.into())
+	}
+
+}
+impl<T: Config> frame_support::unsigned::ValidateUnsigned for Module<T> {
+	type Call = Call<T>;
+
+	/// Only the next proposal in queue, once it is ready, can be processed without a signer,
+	/// unless `on_initialize` already failed to process it
+	fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+		if let Call::process_proposal_unsigned(dao_id, proposal_index) = call {
+			if Self::next_ready_proposal(*dao_id) != Some(*proposal_index) {
+				return InvalidTransaction::Stale.into();
+			}
+			if StuckProposal::get(*dao_id) == Some(*proposal_index) {
+				return InvalidTransaction::Call.into();
+			}
+			ValidTransaction::with_tag_prefix("MolochProcessProposal")
+				.priority(T::UnsignedPriority::get())
+				.and_provides((dao_id, proposal_index))
+				.longevity(5)
+				.propagate(true)
+				.build()
+		} else {
+			InvalidTransaction::Call.into()
+		}
+	}
+}
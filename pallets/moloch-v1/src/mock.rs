@@ -1,19 +1,28 @@
-use crate::{Module, Config};
+use crate::{Module, Config, BlockNumberClock, Fungibles};
 use frame_system as system;
 use sp_core::H256;
-use frame_support::{impl_outer_origin, impl_outer_event, parameter_types, weights::Weight};
+use frame_support::{impl_outer_origin, impl_outer_event, impl_outer_dispatch, parameter_types, weights::Weight, ensure, storage::unhashed, codec::Encode, traits::Get};
 use sp_runtime::{
-	Perbill, ModuleId,
-	testing::Header,
+	Perbill, ModuleId, DispatchError, DispatchResult,
+	testing::{Header, TestXt},
+	transaction_validity::TransactionPriority,
 	traits::{BlakeTwo256, IdentityLookup},
 };
 
 impl_outer_origin! {
-	pub enum Origin for Test {}
+	pub enum Origin for Test {
+		moloch_v1,
+	}
 }
 
 mod moloch_v1 {
-	pub use crate::Event;
+	pub use crate::{Event, Origin};
+}
+
+impl_outer_dispatch! {
+	pub enum Call for Test where origin: Origin {
+		frame_system::System,
+	}
 }
 
 impl_outer_event! {
@@ -44,17 +53,26 @@ parameter_types! {
     pub const MaxGracePeriodLength: u128 = 100_000_000; // maximum length of grace period
     pub const MaxDilutionBound: u128 = 100_000_000; // maximum dilution bound
     pub const MaxShares: u128 = 100_000_000; // maximum number of shares that can be minted
+	pub const MaxWhitelistedTokens: u32 = 2;
+	pub const MaxDelegators: u32 = 2;
+	pub const MaxDetailsLength: u32 = 32;
+	// blocks are a second apart
+	pub const MinimumPeriod: u64 = 500;
+	// room for two proposals
+	pub const AutoProcessWeight: Weight = 25_000;
+	pub const UnsignedPriority: TransactionPriority = 1 << 20;
 }
 
 impl system::Trait for Test {
 	type BaseCallFilter = ();
 	type Origin = Origin;
-	type Call = ();
+	type Call = Call;
 	type Index = u64;
 	type BlockNumber = u64;
 	type Hash = H256;
 	type Hashing = BlakeTwo256;
-	type AccountId = u64;
+	// wide enough to tell apart the sub accounts of different guilds
+	type AccountId = u128;
 	type Lookup = IdentityLookup<Self::AccountId>;
 	type Header = Header;
 	type Event = Event;
@@ -90,7 +108,7 @@ impl pallet_balances::Trait for Test {
 impl pallet_timestamp::Trait for Test {
     type Moment = u64;
     type OnTimestampSet = ();
-    type MinimumPeriod = ();
+    type MinimumPeriod = MinimumPeriod;
     type WeightInfo = ();
 }
 
@@ -105,7 +123,10 @@ impl Config for Test {
 
 	type Event = Event;
 
-	type AdminOrigin = frame_system::EnsureRoot<u64>;
+	type AdminOrigin = frame_system::EnsureRoot<u128>;
+
+	// periods are counted in blocks, so tests only need to move the block number
+	type PeriodClock = BlockNumberClock<Test>;
 
 	// maximum length of voting period
 	type MaxVotingPeriodLength = MaxVotingPeriodLength;
@@ -119,6 +140,111 @@ impl Config for Test {
 	// maximum number of shares
 	type MaxShares = MaxShares;
 
+	type Assets = MockAssets;
+
+	// maximum number of whitelisted tokens of a guild
+	type MaxWhitelistedTokens = MaxWhitelistedTokens;
+
+	type Origin = Origin;
+
+	type Action = Call;
+
+	// maximum weight of the call of an action proposal
+	type MaxActionWeight = MaxActionWeight;
+
+	// maximum number of members delegating their vote to the same member
+	type MaxDelegators = MaxDelegators;
+
+	// maximum length of the details of a proposal and of the uri of its content
+	type MaxDetailsLength = MaxDetailsLength;
+
+	type AutoProcessWeight = AutoProcessWeight;
+
+	type UnsignedPriority = UnsignedPriority;
+}
+
+/// Unsigned transactions of the off-chain worker
+pub type Extrinsic = TestXt<crate::Call<Test>, ()>;
+
+impl system::offchain::SendTransactionTypes<crate::Call<Test>> for Test {
+	type OverarchingCall = crate::Call<Test>;
+	type Extrinsic = Extrinsic;
+}
+
+/// Stub of an assets pallet, balances live in the test externalities
+/// `MaxActionWeight` of the mock, tests lower it to mimic a runtime upgrade
+pub struct MaxActionWeight;
+
+impl MaxActionWeight {
+	pub fn set(weight: Weight) {
+		unhashed::put(b"mock_max_action_weight", &weight);
+	}
+}
+
+impl Get<Weight> for MaxActionWeight {
+	fn get() -> Weight {
+		unhashed::get_or(b"mock_max_action_weight", 512)
+	}
+}
+
+pub struct MockAssets;
+
+impl MockAssets {
+	fn key(asset: u32, who: &u128) -> Vec<u8> {
+		(b"mock_assets", asset, who).encode()
+	}
+
+	fn reserved_key(asset: u32, who: &u128) -> Vec<u8> {
+		(b"mock_assets_reserved", asset, who).encode()
+	}
+
+	pub fn set_balance(asset: u32, who: &u128, amount: u128) {
+		unhashed::put(&Self::key(asset, who), &amount);
+	}
+
+	fn set_reserved_balance(asset: u32, who: &u128, amount: u128) {
+		unhashed::put(&Self::reserved_key(asset, who), &amount);
+	}
+}
+
+impl Fungibles<u128> for MockAssets {
+	type AssetId = u32;
+
+	fn balance(asset: u32, who: &u128) -> u128 {
+		unhashed::get_or_default(&Self::key(asset, who))
+	}
+
+	fn reserved_balance(asset: u32, who: &u128) -> u128 {
+		unhashed::get_or_default(&Self::reserved_key(asset, who))
+	}
+
+	fn transfer(asset: u32, source: &u128, dest: &u128, amount: u128) -> DispatchResult {
+		let source_balance = Self::balance(asset, source);
+		ensure!(source_balance >= amount, DispatchError::Other("InsufficientBalance"));
+		Self::set_balance(asset, source, source_balance - amount);
+		Self::set_balance(asset, dest, Self::balance(asset, dest) + amount);
+		Ok(())
+	}
+
+	fn reserve(asset: u32, who: &u128, amount: u128) -> DispatchResult {
+		let balance = Self::balance(asset, who);
+		ensure!(balance >= amount, DispatchError::Other("InsufficientBalance"));
+		Self::set_balance(asset, who, balance - amount);
+		Self::set_reserved_balance(asset, who, Self::reserved_balance(asset, who) + amount);
+		Ok(())
+	}
+
+	fn unreserve(asset: u32, who: &u128, amount: u128) -> DispatchResult {
+		Self::repatriate_reserved(asset, who, who, amount)
+	}
+
+	fn repatriate_reserved(asset: u32, source: &u128, dest: &u128, amount: u128) -> DispatchResult {
+		let reserved = Self::reserved_balance(asset, source);
+		ensure!(reserved >= amount, DispatchError::Other("InsufficientReserve"));
+		Self::set_reserved_balance(asset, source, reserved - amount);
+		Self::set_balance(asset, dest, Self::balance(asset, dest) + amount);
+		Ok(())
+	}
 }
 
 pub type System = frame_system::Module<Test>;
@@ -126,13 +252,16 @@ pub type Balances = pallet_balances::Module<Test>;
 pub type MolochV1 = Module<Test>;
 pub type Timestamp = pallet_timestamp::Module<Test>;
 
+/// Account too poor to pay for anything in the tests
+pub const POOR: u128 = 4;
+
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	// system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
 	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
 	pallet_balances::GenesisConfig::<Test>{
 		// Total issuance will be 1000 with internal account initialized at ED.
-		balances: vec![(0, 1000), (1, 2000), (2, 3000), (3, 4000)],
+		balances: vec![(0, 1000), (1, 2000), (2, 3000), (3, 4000), (POOR, 10)],
 	}.assimilate_storage(&mut t).unwrap();
 	system::GenesisConfig::default().assimilate_storage::<Test>(&mut t).unwrap();
 	t.into()
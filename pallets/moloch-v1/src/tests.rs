@@ -1,10 +1,25 @@
-use crate::{Error, mock::*};
-use frame_support::{assert_ok, assert_noop};
+use crate::{
+	Error, DaoId, ProposalStatus, Proposal, Releases, PeriodClock, TimestampClock, Fungibles,
+	EnsureMolochMajority, RawOrigin, GuildGenesis, GuildParameters, ProposalCategory, VotingThreshold, StorageVersion, ApplicantCustody,
+	TotalSharesRequested, AddressOfDelegates, ProposalVotes, ShareCheckpoints, TotalShareCheckpoints, Ballot, Vote, ProposalContent,
+	LegacyMember, LegacyProposal, Proposals, ProposalCount, LockedTributes, mock::*,
+};
+use frame_support::{
+	assert_ok, assert_noop, parameter_types, StorageValue, StorageMap, StorageDoubleMap, StorageHasher, Blake2_128Concat,
+	codec::{Encode, Decode},
+	storage::{unhashed, migration::{put_storage_value, get_storage_value}},
+	traits::{OnRuntimeUpgrade, OnInitialize, OffchainWorker, Currency, ReservableCurrency, EnsureOrigin, Get},
+	unsigned::ValidateUnsigned,
+};
+use sp_core::H256;
+use sp_core::offchain::{OffchainExt, TransactionPoolExt, testing::{TestOffchainExt, TestTransactionPoolExt}};
+use sp_runtime::{DispatchError, traits::{BadOrigin, AccountIdConversion}, transaction_validity::{TransactionSource, InvalidTransaction}};
+use pallet_balances::{Error as BalancesError, DefaultInstance};
 use super::RawEvent;
 use sp_std::convert::{TryInto};
 
 
-fn last_event() -> RawEvent<u64, u64> {
+fn last_event() -> RawEvent<u128, u64, u32> {
 	System::events().into_iter().map(|r| r.event)
 		.filter_map(|e| {
 			if let Event::moloch_v1(inner) = e { Some(inner) } else { None }
@@ -13,9 +28,15 @@ fn last_event() -> RawEvent<u64, u64> {
 		.unwrap()
 }
 
+/// Move to the given period of a guild, the mock counts periods in blocks
+fn run_to_period(dao_id: DaoId, period: u128) {
+	let block = MolochV1::summon_time(dao_id) + period * MolochV1::period_duration(dao_id) as u128;
+	System::set_block_number(TryInto::<u64>::try_into(block).ok().unwrap());
+}
+
 /// A helper function to summon moloch for each test case
-fn summon_with(initial_member: u64) {
-	// in seconds
+fn summon_with(initial_member: u128) {
+	// in blocks
 	let period_duration = 10;
 	let voting_period_length = 2;
 	let grace_period_length = 2;
@@ -30,7 +51,7 @@ fn summon_with(initial_member: u64) {
 #[test]
 fn summon_works() {
 	new_test_ext().execute_with(|| {
-		// in seconds
+		// in blocks
 		let period_duration = 10;
 		let voting_period_length = 2;
 		let grace_period_length = 2;
@@ -40,18 +61,54 @@ fn summon_works() {
 		let processing_reward = 50;
 
 		assert_ok!(MolochV1::summon(Origin::signed(1), period_duration, voting_period_length, grace_period_length, abort_window, dilution_bound, proposal_deposit, processing_reward));
+		let dao_id = 0;
+		assert_eq!(MolochV1::dao_count(), 1);
 		// check the constants
-		assert_eq!(MolochV1::period_duration(), period_duration);
-		assert_eq!(MolochV1::voting_period_length(), voting_period_length);
-		assert_eq!(MolochV1::grace_period_length(), grace_period_length);
-		assert_eq!(MolochV1::abort_window(), abort_window);
-		assert_eq!(MolochV1::dilution_bound(), dilution_bound);
-		assert_eq!(MolochV1::proposal_deposit(), proposal_deposit);
-		assert_eq!(MolochV1::processing_reward(), processing_reward);
+		assert_eq!(MolochV1::period_duration(dao_id), period_duration);
+		assert_eq!(MolochV1::voting_period_length(dao_id), voting_period_length);
+		assert_eq!(MolochV1::grace_period_length(dao_id), grace_period_length);
+		assert_eq!(MolochV1::abort_window(dao_id), abort_window);
+		assert_eq!(MolochV1::dilution_bound(dao_id), dilution_bound);
+		assert_eq!(MolochV1::proposal_deposit(dao_id), proposal_deposit);
+		assert_eq!(MolochV1::processing_reward(dao_id), processing_reward);
 
 		// check the shares and member
-		assert_eq!(MolochV1::totoal_shares(), 1);
-		assert_eq!(MolochV1::members(1).exists, true);
+		assert_eq!(MolochV1::totoal_shares(dao_id), 1);
+		assert_eq!(MolochV1::members(dao_id, 1).exists, true);
+	});
+}
+
+#[test]
+fn summon_multiple_daos_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		summon_with(1);
+		summon_with(2);
+		assert_eq!(MolochV1::dao_count(), 2);
+		assert_eq!(last_event(), RawEvent::SummonComplete(1, 2, 1));
+
+		// members and accounts are isolated per guild
+		assert_eq!(MolochV1::members(0, 1).exists, true);
+		assert_eq!(MolochV1::members(0, 2).exists, false);
+		assert_eq!(MolochV1::members(1, 2).exists, true);
+		assert_eq!(MolochV1::members(1, 1).exists, false);
+		assert_ne!(MolochV1::account_id(0), MolochV1::account_id(1));
+
+		// a proposal in one guild does not show up in the other
+		let applicant = 3;
+		assert_ok!(MolochV1::custody(Origin::signed(applicant), 0, 50));
+		assert_ok!(MolochV1::submit_proposal(Origin::signed(1), 0, applicant, 50, 5, 0, b"test_proposal".to_vec(), None));
+		assert_eq!(MolochV1::proposal_count(0), 1);
+		assert_eq!(MolochV1::proposal_count(1), 0);
+		assert_eq!(MolochV1::totoal_shares_requested(1), 0);
+
+		// members of one guild can not propose in another guild
+		assert_noop!(
+			MolochV1::submit_proposal(Origin::signed(1), 1, applicant, 50, 5, 0, b"test_proposal".to_vec(), None),
+			Error::<Test>::NotMember
+		);
+		// unknown guild
+		assert_noop!(MolochV1::custody(Origin::signed(applicant), 2, 50), Error::<Test>::DaoNotExist);
 	});
 }
 
@@ -62,6 +119,7 @@ fn submit_proposal_works() {
 		System::set_block_number(1);
 		let initial_member = 1;
 		summon_with(initial_member);
+		let dao_id = 0;
 
 		// failed when member propose for applicant who did not deposit in custody account
 		let token_tribute = 50;
@@ -69,14 +127,38 @@ fn submit_proposal_works() {
 		let applicant = 2;
 		let detail = b"test_proposal".to_vec();
 		assert_noop!(
-			MolochV1::submit_proposal(Origin::signed(1), applicant, token_tribute, shares_requested, detail.clone()),
+			MolochV1::submit_proposal(Origin::signed(1), dao_id, applicant, token_tribute, shares_requested, 0, detail.clone(), None),
 			Error::<Test>::NoCustodyFound
 		);
 
 		// deposit custody and resubmit
-		assert_ok!(MolochV1::custody(Origin::signed(applicant), token_tribute));
-		assert_ok!(MolochV1::submit_proposal(Origin::signed(1), applicant, token_tribute, shares_requested, detail));
-		assert_eq!(last_event(), RawEvent::SubmitProposal(0, 1, 1, applicant, token_tribute.into(), shares_requested));
+		assert_ok!(MolochV1::custody(Origin::signed(applicant), dao_id, token_tribute));
+		assert_ok!(MolochV1::submit_proposal(Origin::signed(1), dao_id, applicant, token_tribute, shares_requested, 0, detail, None));
+		assert_eq!(last_event(), RawEvent::SubmitProposal(dao_id, 0, 1, 1, applicant, token_tribute.into(), shares_requested, 0));
+	});
+}
+
+#[test]
+fn proposal_details_are_checked() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		summon_with(1);
+		let dao_id = 0;
+		let content = |uri: &[u8]| Some(ProposalContent { hash: H256::repeat_byte(1), uri: uri.to_vec() });
+		assert_noop!(MolochV1::submit_whitelist_proposal(Origin::signed(1), dao_id, 1, vec![b'a'; 33], None), Error::<Test>::DetailsTooLong);
+		assert_noop!(MolochV1::submit_whitelist_proposal(Origin::signed(1), dao_id, 1, vec![0xff], None), Error::<Test>::InvalidDetails);
+		assert_noop!(MolochV1::submit_whitelist_proposal(Origin::signed(1), dao_id, 1, vec![], content(&[b'a'; 33])), Error::<Test>::DetailsTooLong);
+		assert_noop!(MolochV1::submit_whitelist_proposal(Origin::signed(1), dao_id, 1, vec![], content(b"ipfs:// bafy")), Error::<Test>::InvalidDetails);
+		assert_noop!(MolochV1::submit_whitelist_proposal(Origin::signed(1), dao_id, 1, vec![], content(b"")), Error::<Test>::InvalidDetails);
+
+		// the body lives off-chain, the chain keeps its hash
+		assert_ok!(MolochV1::submit_whitelist_proposal(Origin::signed(1), dao_id, 1, "liste blanche ✓".as_bytes().to_vec(), content(b"ipfs://bafy")));
+		assert_eq!(MolochV1::proposal_content(dao_id, 0), content(b"ipfs://bafy"));
+		// and carries it through the sponsorship
+		assert_ok!(MolochV1::propose(Origin::signed(2), dao_id, 0, 1, 0, 0, b"apply".to_vec(), content(b"ipfs://apply")));
+		assert_ok!(MolochV1::sponsor_proposal(Origin::signed(1), dao_id, 0));
+		assert_eq!(MolochV1::proposal_content(dao_id, 1), content(b"ipfs://apply"));
+		assert_eq!(MolochV1::proposal_content(dao_id, 2), None);
 	});
 }
 
@@ -87,6 +169,7 @@ fn add_member_works() {
 		System::set_block_number(1);
 		let initial_member = 1;
 		summon_with(initial_member);
+		let dao_id = 0;
 
 		// failed when member propose for applicant who did not deposit in custody account
 		let token_tribute = 50;
@@ -94,27 +177,1442 @@ fn add_member_works() {
 		let applicant = 2;
 		let detail = b"test_proposal".to_vec();
 		// deposit custody and submit
-		assert_ok!(MolochV1::custody(Origin::signed(applicant), token_tribute));
-		assert_ok!(MolochV1::submit_proposal(Origin::signed(1), applicant, token_tribute, shares_requested, detail));
+		assert_ok!(MolochV1::custody(Origin::signed(applicant), dao_id, token_tribute));
+		assert_ok!(MolochV1::submit_proposal(Origin::signed(1), dao_id, applicant, token_tribute, shares_requested, 0, detail, None));
 
-		// set the timestamp to make voting period effect
-		let now = Timestamp::now();
-		let period_duration = TryInto::<u64>::try_into(MolochV1::period_duration() * 1000 * 2).ok().unwrap();
-		Timestamp::set_timestamp(now + period_duration);
+		// move on to make voting period effect
+		run_to_period(dao_id, 2);
 
 		// vote yes
-		assert_ok!(MolochV1::submit_vote(Origin::signed(1), 0, 1));
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 0, 1));
 		 
 		// pass grace period
-		Timestamp::set_timestamp(now + period_duration * 4);
+		run_to_period(dao_id, 8);
 		let processor = 3;
 		let balance_before = Balances::free_balance(processor);
-		let processing_reward = MolochV1::processing_reward();
-		assert_ok!(MolochV1::process_proposal(Origin::signed(processor), 0));
+		let processing_reward = MolochV1::processing_reward(dao_id);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(processor), dao_id, 0));
 		// make sure the processor get rewarded
 		assert_eq!(Balances::free_balance(processor), processing_reward + balance_before);
 
 		// check the applicant has become a member
-		assert_eq!(MolochV1::members(applicant).exists, true);
+		assert_eq!(MolochV1::members(dao_id, applicant).exists, true);
+	});
+}
+
+#[test]
+fn runtime_api_queries_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(MolochV1::current_period(0), None);
+		summon_with(1);
+		let dao_id = 0;
+		assert_eq!(MolochV1::current_period(dao_id), Some(0));
+		assert_eq!(MolochV1::member_info(dao_id, 1), Some((1, 0, None, 1)));
+		assert_eq!(MolochV1::member_info(dao_id, 2), None);
+		assert_eq!(MolochV1::proposal_status(dao_id, 0), None);
+
+		let applicant = 2;
+		assert_ok!(MolochV1::custody(Origin::signed(applicant), dao_id, 50));
+		assert_ok!(MolochV1::submit_proposal(Origin::signed(1), dao_id, applicant, 50, 5, 0, b"test_proposal".to_vec(), None));
+		assert_eq!(MolochV1::proposal_status(dao_id, 0), Some(ProposalStatus::Pending));
+
+		// the proposal starts at period 1, votes for 2 periods and then waits 2 periods of grace
+		run_to_period(dao_id, 1);
+		assert_eq!(MolochV1::proposal_status(dao_id, 0), Some(ProposalStatus::Voting));
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 0, 1));
+		run_to_period(dao_id, 3);
+		assert_eq!(MolochV1::proposal_status(dao_id, 0), Some(ProposalStatus::Grace));
+		run_to_period(dao_id, 5);
+		assert_eq!(MolochV1::proposal_status(dao_id, 0), Some(ProposalStatus::Ready));
+		assert_ok!(MolochV1::process_proposal(Origin::signed(3), dao_id, 0));
+		assert_eq!(MolochV1::proposal_status(dao_id, 0), Some(ProposalStatus::Processed));
+
+		// the bank holds the tribute and its existential deposit, the tribute is shared by 6 shares
+		let bank = MolochV1::guild_bank_balance(dao_id);
+		assert_eq!(bank, 51);
+		assert_eq!(MolochV1::distributable_balance(dao_id), 50);
+		assert_eq!(MolochV1::ragequit_preview(dao_id, 3), 50 * 3 / 6);
+		assert_eq!(MolochV1::ragequit_preview(dao_id, 7), 0);
+	});
+}
+
+#[test]
+fn migrate_to_multiple_guilds_works() {
+	new_test_ext().execute_with(|| {
+		// the single guild layout before `Releases::V2`
+		let key = |who: u128| who.using_encoded(Blake2_128Concat::hash);
+		put_storage_value(b"MolochV1", b"PeriodDuration", &[], 10u32);
+		put_storage_value(b"MolochV1", b"TotalShares", &[], 5u128);
+		put_storage_value(b"MolochV1", b"VotingPeriodLength", &[], 2u128);
+		put_storage_value(b"MolochV1", b"ProposalDeposit", &[], 100u64);
+		put_storage_value(b"MolochV1", b"Members", &key(1), LegacyMember { shares: 5, highest_index_yes_vote: 0, exists: true, delegate_key: 3u128 });
+		put_storage_value(b"MolochV1", b"Members", &key(2), LegacyMember { shares: 0, highest_index_yes_vote: 0, exists: true, delegate_key: 2u128 });
+		put_storage_value(b"MolochV1", b"AddressOfDelegates", &key(3), 1u128);
+		put_storage_value(b"MolochV1", b"ApplicantCustody", &key(2), 50u64);
+		put_storage_value(b"MolochV1", b"ProposalVotes", &[key(0), key(1)].concat(), 1u8);
+		let legacy_bank: u128 = MolochV1ModuleId::get().into_account();
+		let legacy_custody: u128 = MolochV1ModuleId::get().into_sub_account("custody");
+		let _ = Balances::deposit_creating(&legacy_bank, 500);
+		let _ = Balances::deposit_creating(&legacy_custody, 50);
+
+		MolochV1::on_runtime_upgrade();
+
+		assert_eq!(MolochV1::storage_version(), Releases::V6);
+		assert_eq!(MolochV1::dao_count(), 1);
+		assert_eq!(MolochV1::period_duration(0), 10);
+		assert_eq!(MolochV1::totoal_shares(0), 5);
+		assert_eq!(MolochV1::voting_period_length(0), 2);
+		assert_eq!(MolochV1::proposal_deposit(0), 100);
+		assert!(get_storage_value::<u128>(b"MolochV1", b"TotalShares", &[]).is_none());
+		assert_eq!(MolochV1::members(0, 1).delegate_key, 3);
+		// only the member who voted YES on the first proposal waits for it
+		assert_eq!(MolochV1::members(0, 1).highest_index_yes_vote, Some(0));
+		assert_eq!(MolochV1::members(0, 2).highest_index_yes_vote, None);
+		assert_eq!(MolochV1::address_of_delegate(0, 3), 1);
+		assert_eq!(MolochV1::proposal_vote((0, 0), 1), Ballot { vote: Vote::Yes, weight: 5, delegators: vec![] });
+		assert_eq!(Balances::free_balance(legacy_bank), 0);
+		assert_eq!(Balances::free_balance(MolochV1::account_id(0)), 500);
+		// the tribute held in custody ends up reserved on the applicant
+		assert_eq!(Balances::free_balance(legacy_custody), 0);
+		assert_eq!(Balances::reserved_balance(2), 50);
+		assert_eq!(MolochV1::applicant_custody(0, 2), 50);
+	});
+}
+
+#[test]
+fn migrate_proposal_queue_works() {
+	new_test_ext().execute_with(|| {
+		let proposal = |starting_period: u128, processed: bool| LegacyProposal {
+			proposer: 1,
+			applicant: 2,
+			starting_period: starting_period,
+			processed: processed,
+			token_tribute: 50,
+			..Default::default()
+		};
+		put_storage_value(b"MolochV1", b"ProposalDeposit", &[], 100u64);
+		put_storage_value(b"MolochV1", b"PeriodDuration", &[], 10u32);
+		put_storage_value(b"MolochV1", b"VotingPeriodLength", &[], 2u128);
+		put_storage_value(b"MolochV1", b"GracePeriodLength", &[], 3u128);
+		put_storage_value(b"MolochV1", b"ProposalQueue", &[], vec![proposal(1, true), proposal(2, false), proposal(3, false)]);
+		// the deposits of the unprocessed proposals were paid into the guild bank
+		let legacy_bank: u128 = MolochV1ModuleId::get().into_account();
+		let _ = Balances::deposit_creating(&legacy_bank, 201);
+		assert_eq!(MolochV1::storage_version(), Releases::V1);
+
+		MolochV1::on_runtime_upgrade();
+
+		assert_eq!(MolochV1::storage_version(), Releases::V6);
+		assert!(get_storage_value::<Vec<LegacyProposal<u128>>>(b"MolochV1", b"ProposalQueue", &[]).is_none());
+		assert_eq!(MolochV1::proposal_count(0), 3);
+		assert_eq!(MolochV1::processed_count(0), 1);
+		let migrated = MolochV1::proposals(0, 2);
+		assert_eq!((migrated.starting_period, migrated.token_tribute, migrated.processed), (3, 50, false));
+		// the deposit of the unprocessed proposals is reserved on their sponsor
+		assert_eq!((migrated.deposit, migrated.depositor), (100, 1));
+		assert_eq!((migrated.voting_period_length, migrated.grace_period_length), (2, 3));
+		assert_eq!(MolochV1::proposals(0, 0).deposit, 0);
+		assert_eq!(Balances::reserved_balance(1), 200);
+		assert_eq!(Balances::free_balance(MolochV1::account_id(0)), 1);
+	});
+}
+
+#[test]
+fn migrate_proposal_queue_keeps_undecodable_queue() {
+	new_test_ext().execute_with(|| {
+		put_storage_value(b"MolochV1", b"ProposalQueue", &[], vec![1u8, 2, 3]);
+
+		MolochV1::on_runtime_upgrade();
+
+		assert_eq!(MolochV1::storage_version(), Releases::V6);
+		assert_eq!(get_storage_value::<Vec<u8>>(b"MolochV1", b"ProposalQueue", &[]), Some(vec![1, 2, 3]));
+		assert_eq!(MolochV1::proposal_count(0), 0);
+	});
+}
+
+#[test]
+fn migrate_summon_time_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1_000);
+		Timestamp::set_timestamp(600_000);
+		// summoned five minutes ago, with periods of a minute
+		put_storage_value(b"MolochV1", b"PeriodDuration", &[], 60u32);
+		put_storage_value(b"MolochV1", b"SummonTime", &[], 300_000u64);
+
+		MolochV1::on_runtime_upgrade();
+
+		assert_eq!(MolochV1::storage_version(), Releases::V6);
+		// the mock counts blocks, which are a second apart
+		assert_eq!(MolochV1::summon_time(0), 700);
+		assert_eq!(MolochV1::period_duration(0), 60);
+		assert_eq!(MolochV1::get_current_period(0), Ok(5));
+		// the timestamp clock would have counted seconds
+		assert_eq!(TimestampClock::<Test>::at_timestamp(300_000), 300);
+	});
+}
+
+#[test]
+fn period_clocks_work() {
+	new_test_ext().execute_with(|| {
+		// the timestamp clock counts seconds
+		Timestamp::set_timestamp(12_345);
+		assert_eq!(TimestampClock::<Test>::now(), 12);
+
+		// the mock counts periods of 10 blocks from the summon block
+		System::set_block_number(5);
+		summon_with(1);
+		assert_eq!(MolochV1::summon_time(0), 5);
+		assert_eq!(MolochV1::get_current_period(0), Ok(0));
+		System::set_block_number(14);
+		assert_eq!(MolochV1::get_current_period(0), Ok(0));
+		System::set_block_number(15);
+		assert_eq!(MolochV1::get_current_period(0), Ok(1));
+		// timestamps do not matter any more
+		Timestamp::set_timestamp(1_000_000);
+		assert_eq!(MolochV1::get_current_period(0), Ok(1));
+	});
+}
+
+#[test]
+fn sponsored_proposal_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		summon_with(1);
+		let dao_id = 0;
+		let applicant = 2;
+		let token_tribute = 50;
+		let balance_before = Balances::free_balance(applicant);
+
+		// anyone can apply, the tribute is locked by the proposal right away
+		assert_ok!(MolochV1::propose(Origin::signed(applicant), dao_id, token_tribute, 5, 0, 0, b"test_proposal".to_vec(), None));
+		assert_eq!(last_event(), RawEvent::Propose(dao_id, 0, applicant, token_tribute.into(), 5, 0, 0));
+		assert_eq!(Balances::free_balance(applicant), balance_before - token_tribute);
+		assert_eq!(Balances::reserved_balance(applicant), token_tribute);
+		assert_eq!(MolochV1::applicant_custody(dao_id, applicant), 0);
+		assert_eq!(MolochV1::proposal_count(dao_id), 0);
+
+		// only members can sponsor
+		assert_noop!(MolochV1::sponsor_proposal(Origin::signed(3), dao_id, 0), Error::<Test>::NotMember);
+		let sponsor_before = Balances::free_balance(1);
+		assert_ok!(MolochV1::sponsor_proposal(Origin::signed(1), dao_id, 0));
+		assert_eq!(last_event(), RawEvent::SponsorProposal(dao_id, 0, 0, 1, 1));
+		assert_eq!(Balances::free_balance(1), sponsor_before - MolochV1::proposal_deposit(dao_id));
+		assert_eq!(MolochV1::unsponsored_proposals(dao_id, 0).proposer, 0);
+		assert_eq!(MolochV1::proposal_count(dao_id), 1);
+		assert_eq!(MolochV1::proposals(dao_id, 0).proposer, 1);
+		// a sponsored proposal can not be cancelled any more
+		assert_noop!(MolochV1::cancel_proposal(Origin::signed(applicant), dao_id, 0), Error::<Test>::ProposalNotExist);
+
+		// and goes through the normal pipeline
+		run_to_period(dao_id, 1);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 0, 1));
+		run_to_period(dao_id, 5);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(3), dao_id, 0));
+		assert_eq!(MolochV1::members(dao_id, applicant).shares, 5);
+	});
+}
+
+#[test]
+fn cancel_unsponsored_proposal_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		summon_with(1);
+		let dao_id = 0;
+		let applicant = 2;
+		let balance_before = Balances::free_balance(applicant);
+		assert_ok!(MolochV1::propose(Origin::signed(applicant), dao_id, 50, 5, 0, 0, b"test_proposal".to_vec(), None));
+
+		// only the proposer can cancel
+		assert_noop!(MolochV1::cancel_proposal(Origin::signed(1), dao_id, 0), Error::<Test>::NotProposalProposer);
+		assert_ok!(MolochV1::cancel_proposal(Origin::signed(applicant), dao_id, 0));
+		assert_eq!(last_event(), RawEvent::CancelProposal(dao_id, 0, applicant));
+		// full refund
+		assert_eq!(Balances::free_balance(applicant), balance_before);
+		assert_eq!(MolochV1::applicant_custody(dao_id, applicant), 0);
+		assert_noop!(MolochV1::sponsor_proposal(Origin::signed(1), dao_id, 0), Error::<Test>::ProposalNotExist);
+	});
+}
+
+#[test]
+fn stale_proposals_expire() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(MolochV1::summon_with(Origin::signed(0), 10, 2, 2, 1, 1, 100, 50, vec![(1, 6, 0), (2, 3, 0)], 0));
+		let dao_id = 0;
+		assert_ok!(MolochV1::custody(Origin::signed(3), dao_id, 50));
+		assert_ok!(MolochV1::submit_proposal(Origin::signed(1), dao_id, 3, 50, 5, 0, b"test_proposal".to_vec(), None));
+		assert_ok!(MolochV1::submit_whitelist_proposal(Origin::signed(2), dao_id, 1, b"whitelist".to_vec(), None));
+		assert_ok!(MolochV1::submit_whitelist_proposal(Origin::signed(1), dao_id, 2, b"whitelist".to_vec(), None));
+
+		// only the member who submitted it can cancel, and gets the whole deposit back
+		assert_noop!(MolochV1::cancel_queued_proposal(Origin::signed(2), dao_id, 0), Error::<Test>::NotProposalProposer);
+		assert_ok!(MolochV1::cancel_queued_proposal(Origin::signed(1), dao_id, 0));
+		assert_eq!(last_event(), RawEvent::CancelQueuedProposal(dao_id, 0, 1));
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert_eq!(Balances::reserved_balance(1), 100);
+		// the requested shares are released with it, and only once
+		assert_eq!(MolochV1::totoal_shares_requested(dao_id), 0);
+		assert_eq!(MolochV1::proposals(dao_id, 0).shares_requested, 0);
+		assert_noop!(MolochV1::cancel_queued_proposal(Origin::signed(1), dao_id, 0), Error::<Test>::ProposalHasAborted);
+
+		// a cancelled proposal expires right away
+		assert_ok!(MolochV1::expire_proposals(Origin::signed(3), dao_id, 10));
+		assert_eq!(last_event(), RawEvent::ProposalExpired(dao_id, 0));
+		assert!(MolochV1::proposals(dao_id, 0).processed);
+		assert_eq!(MolochV1::processed_count(dao_id), 1);
+		assert_eq!(MolochV1::totoal_shares_requested(dao_id), 0);
+		assert_noop!(MolochV1::expire_proposals(Origin::signed(3), dao_id, 10), Error::<Test>::NoStaleProposal);
+
+		// voting can not be cancelled, but a proposal nobody voted yes on expires when it ends
+		run_to_period(dao_id, 2);
+		assert_noop!(MolochV1::cancel_queued_proposal(Origin::signed(2), dao_id, 1), Error::<Test>::ProposalHasStarted);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 1, 2));
+		run_to_period(dao_id, 3);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 2, 1));
+		assert_noop!(MolochV1::expire_proposals(Origin::signed(3), dao_id, 10), Error::<Test>::NoStaleProposal);
+		run_to_period(dao_id, 4);
+		assert_ok!(MolochV1::expire_proposals(Origin::signed(3), dao_id, 10));
+		assert!(!MolochV1::proposals(dao_id, 1).did_pass);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		// the next one may still pass
+		assert_eq!(MolochV1::processed_count(dao_id), 2);
+		assert_noop!(MolochV1::process_proposal(Origin::signed(3), dao_id, 2), Error::<Test>::ProposalNotReady);
+	});
+}
+
+#[test]
+fn delegate_keys_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(MolochV1::summon_with(Origin::signed(0), 10, 2, 2, 1, 1, 100, 50, vec![(1, 6, 0), (2, 3, 0)], 0));
+		let dao_id = 0;
+		assert_noop!(MolochV1::update_delegate(Origin::signed(5), dao_id, 6), Error::<Test>::NotMember);
+		assert_ok!(MolochV1::update_delegate(Origin::signed(1), dao_id, 7));
+		assert_eq!(last_event(), RawEvent::UpdateDelegateKey(dao_id, 1, 7));
+		assert_eq!(MolochV1::members(dao_id, 1).delegate_key, 7);
+		assert_eq!(MolochV1::address_of_delegate(dao_id, 7), 1);
+		assert!(!AddressOfDelegates::<Test>::contains_key(dao_id, 1));
+		// keys can not be shared, nor be the address of a member
+		assert_noop!(MolochV1::update_delegate(Origin::signed(2), dao_id, 7), Error::<Test>::NoOverwriteDelegate);
+		assert_noop!(MolochV1::update_delegate(Origin::signed(2), dao_id, 1), Error::<Test>::NoOverwriteMember);
+
+		// the delegate acts for the member, the member address does not any more
+		assert_noop!(MolochV1::submit_whitelist_proposal(Origin::signed(1), dao_id, 1, b"whitelist".to_vec(), None), Error::<Test>::NotMember);
+		assert_ok!(MolochV1::submit_whitelist_proposal(Origin::signed(7), dao_id, 1, b"whitelist".to_vec(), None));
+		assert_eq!(MolochV1::proposals(dao_id, 0).proposer, 1);
+		run_to_period(dao_id, 1);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(7), dao_id, 0, 1));
+		// a new key does not get a second vote
+		assert_ok!(MolochV1::update_delegate(Origin::signed(1), dao_id, 8));
+		assert_noop!(MolochV1::submit_vote(Origin::signed(7), dao_id, 0, 1), Error::<Test>::NotMember);
+		assert_noop!(MolochV1::submit_vote(Origin::signed(8), dao_id, 0, 1), Error::<Test>::MemberHasVoted);
+		assert_eq!(MolochV1::proposals(dao_id, 0).yes_votes, 6);
+
+		// the delegate can step down, giving the key back to the member
+		assert_ok!(MolochV1::revoke_delegate(Origin::signed(8), dao_id));
+		assert_eq!(last_event(), RawEvent::DelegateKeyRevoked(dao_id, 1, 8));
+		assert_eq!(MolochV1::members(dao_id, 1).delegate_key, 1);
+		assert_eq!(MolochV1::address_of_delegate(dao_id, 1), 1);
+		assert!(!AddressOfDelegates::<Test>::contains_key(dao_id, 8));
+		assert_noop!(MolochV1::revoke_delegate(Origin::signed(1), dao_id), Error::<Test>::NoDelegateKey);
+		assert_noop!(MolochV1::revoke_delegate(Origin::signed(8), dao_id), Error::<Test>::NotMember);
+
+		// an applicant joining with the address of a delegate key takes it over
+		assert_ok!(MolochV1::update_delegate(Origin::signed(2), dao_id, 3));
+		assert_ok!(MolochV1::custody(Origin::signed(3), dao_id, 10));
+		assert_ok!(MolochV1::submit_proposal(Origin::signed(1), dao_id, 3, 10, 1, 0, b"test_proposal".to_vec(), None));
+		run_to_period(dao_id, 2);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 1, 1));
+		run_to_period(dao_id, 6);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(1), dao_id, 0));
+		assert_ok!(MolochV1::process_proposal(Origin::signed(1), dao_id, 1));
+		assert!(MolochV1::members(dao_id, 3).exists);
+		assert_eq!(MolochV1::address_of_delegate(dao_id, 3), 3);
+		assert_eq!(MolochV1::members(dao_id, 2).delegate_key, 2);
+		assert_eq!(MolochV1::address_of_delegate(dao_id, 2), 2);
+	});
+}
+
+#[test]
+fn guild_kick_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		summon_with(1);
+		let dao_id = 0;
+		let applicant = 2;
+		// add a member with 5 shares
+		assert_ok!(MolochV1::custody(Origin::signed(applicant), dao_id, 50));
+		assert_ok!(MolochV1::submit_proposal(Origin::signed(1), dao_id, applicant, 50, 5, 0, b"test_proposal".to_vec(), None));
+		run_to_period(dao_id, 1);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 0, 1));
+		run_to_period(dao_id, 5);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(3), dao_id, 0));
+		assert_eq!(MolochV1::totoal_shares(dao_id), 6);
+
+		// only members can propose a kick
+		assert_noop!(
+			MolochV1::submit_guild_kick(Origin::signed(3), dao_id, applicant, b"kick".to_vec(), None),
+			Error::<Test>::NotMember
+		);
+		assert_ok!(MolochV1::submit_guild_kick(Origin::signed(1), dao_id, applicant, b"kick".to_vec(), None));
+		assert_eq!(last_event(), RawEvent::SubmitGuildKick(dao_id, 1, 1, 1, applicant));
+		// the kicked member can not abort it
+		assert_noop!(MolochV1::abort(Origin::signed(applicant), dao_id, 1), Error::<Test>::ProposalNotAbortable);
+
+		run_to_period(dao_id, 6);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 1, 1));
+		run_to_period(dao_id, 10);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(3), dao_id, 1));
+		assert_eq!(MolochV1::proposals(dao_id, 1).did_pass, true);
+
+		// the shares became loot
+		let member = MolochV1::members(dao_id, applicant);
+		assert_eq!((member.shares, member.loot, member.jailed), (0, 5, true));
+		assert_eq!(MolochV1::totoal_shares(dao_id), 1);
+		assert_eq!(MolochV1::total_loot(dao_id), 5);
+
+		// jailed members can not be proposed any more
+		assert_noop!(
+			MolochV1::propose(Origin::signed(applicant), dao_id, 50, 5, 0, 0, b"test_proposal".to_vec(), None),
+			Error::<Test>::MemberJailed
+		);
+		assert_noop!(
+			MolochV1::submit_proposal(Origin::signed(1), dao_id, applicant, 0, 5, 0, b"test_proposal".to_vec(), None),
+			Error::<Test>::MemberJailed
+		);
+
+		// anyone can withdraw the loot for the kicked member
+		let bank = MolochV1::distributable_balance(dao_id);
+		let balance_before = Balances::free_balance(applicant);
+		assert_ok!(MolochV1::ragekick(Origin::signed(3), dao_id, applicant));
+		assert_eq!(last_event(), RawEvent::Ragekick(dao_id, applicant, 5));
+		assert_eq!(Balances::free_balance(applicant), balance_before + bank * 5 / 6);
+		assert_eq!(MolochV1::total_loot(dao_id), 0);
+		assert_noop!(MolochV1::ragekick(Origin::signed(3), dao_id, applicant), Error::<Test>::NoEnoughLoot);
+	});
+}
+
+#[test]
+fn ragekick_needs_a_jailed_member() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(MolochV1::summon_with(Origin::signed(0), 10, 2, 2, 1, 1, 100, 50, vec![(1, 6, 0), (2, 3, 4)], 0));
+		let dao_id = 0;
+		// the loot of a member in good standing is only withdrawn through their own rage quit
+		assert_noop!(MolochV1::ragekick(Origin::signed(3), dao_id, 2), Error::<Test>::MemberNotJailed);
+		assert_eq!(MolochV1::members(dao_id, 2).loot, 4);
+	});
+}
+
+#[test]
+fn loot_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		summon_with(1);
+		let dao_id = 0;
+		let applicant = 2;
+		// ask for 2 shares and 4 loot
+		assert_ok!(MolochV1::custody(Origin::signed(applicant), dao_id, 60));
+		assert_ok!(MolochV1::submit_proposal(Origin::signed(1), dao_id, applicant, 60, 2, 4, b"test_proposal".to_vec(), None));
+		assert_eq!(MolochV1::total_loot_requested(dao_id), 4);
+		run_to_period(dao_id, 1);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 0, 1));
+		run_to_period(dao_id, 5);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(3), dao_id, 0));
+		assert_eq!(MolochV1::total_loot_requested(dao_id), 0);
+		assert_eq!(MolochV1::member_info(dao_id, applicant), Some((2, 4, None, applicant)));
+		assert_eq!(MolochV1::totoal_shares(dao_id), 3);
+		assert_eq!(MolochV1::total_loot(dao_id), 4);
+
+		// loot gives no votes
+		assert_ok!(MolochV1::custody(Origin::signed(3), dao_id, 10));
+		assert_ok!(MolochV1::submit_proposal(Origin::signed(1), dao_id, 3, 10, 1, 0, b"test_proposal".to_vec(), None));
+		run_to_period(dao_id, 6);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(applicant), dao_id, 1, 2));
+		let proposal = MolochV1::proposals(dao_id, 1);
+		assert_eq!(proposal.no_votes, 2);
+		assert_eq!(proposal.max_total_shares_at_yes, 0);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 1, 1));
+		// but counts for the dilution bound
+		assert_eq!(MolochV1::proposals(dao_id, 1).max_total_shares_at_yes, 7);
+
+		// rage quit with loot only, the bank is shared by 3 shares and 4 loot
+		let bank = MolochV1::distributable_balance(dao_id);
+		let balance_before = Balances::free_balance(applicant);
+		assert_noop!(MolochV1::ragequit(Origin::signed(applicant), dao_id, 0, 5), Error::<Test>::NoEnoughLoot);
+		assert_ok!(MolochV1::ragequit(Origin::signed(applicant), dao_id, 0, 4));
+		assert_eq!(last_event(), RawEvent::Ragequit(dao_id, applicant, 0, 4));
+		assert_eq!(Balances::free_balance(applicant), balance_before + bank * 4 / 7);
+		assert_eq!(MolochV1::total_loot(dao_id), 0);
+		assert_eq!(MolochV1::members(dao_id, applicant).shares, 2);
+	});
+}
+
+#[test]
+fn failed_proposals_leave_nothing_behind() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		summon_with(1);
+		let dao_id = 0;
+		let applicant = 2;
+		// the tribute gets reserved, then can not be locked
+		LockedTributes::<Test>::insert((dao_id, None::<u32>), applicant, u128::max_value());
+		assert_noop!(
+			MolochV1::propose(Origin::signed(applicant), dao_id, 100, 1, 0, 0, b"test_proposal".to_vec(), None),
+			Error::<Test>::StorageOverflow
+		);
+		assert_eq!(Balances::reserved_balance(applicant), 0);
+	});
+}
+
+#[test]
+fn whitelisted_token_tribute_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		summon_with(1);
+		let dao_id = 0;
+		let token = 7;
+		let applicant = 2;
+		MockAssets::set_balance(token, &applicant, 500);
+
+		// the guild only takes whitelisted tokens as tribute
+		assert_noop!(
+			MolochV1::propose_with_token(Origin::signed(applicant), dao_id, token, 300, 1, 0, 0, b"test_proposal".to_vec(), None),
+			Error::<Test>::TokenNotWhitelisted
+		);
+		assert_ok!(MolochV1::submit_whitelist_proposal(Origin::signed(1), dao_id, token, b"whitelist".to_vec(), None));
+		assert_eq!(last_event(), RawEvent::SubmitWhitelistProposal(dao_id, 0, 1, 1, token));
+		run_to_period(dao_id, 1);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 0, 1));
+		run_to_period(dao_id, 5);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(3), dao_id, 0));
+		assert_eq!(MolochV1::approved_tokens(dao_id), vec![token]);
+		assert_noop!(
+			MolochV1::submit_whitelist_proposal(Origin::signed(1), dao_id, token, b"whitelist".to_vec(), None),
+			Error::<Test>::TokenAlreadyWhitelisted
+		);
+
+		// a cancelled proposal gets the token back
+		assert_ok!(MolochV1::propose_with_token(Origin::signed(applicant), dao_id, token, 100, 1, 0, 0, b"test_proposal".to_vec(), None));
+		assert_eq!(MockAssets::balance(token, &applicant), 400);
+		assert_ok!(MolochV1::cancel_proposal(Origin::signed(applicant), dao_id, 0));
+		assert_eq!(MockAssets::balance(token, &applicant), 500);
+
+		// the tribute is escrowed, then lands in the guild bank once the proposal passes
+		assert_ok!(MolochV1::propose_with_token(Origin::signed(applicant), dao_id, token, 300, 1, 0, 0, b"test_proposal".to_vec(), None));
+		assert_eq!(last_event(), RawEvent::ProposeWithToken(dao_id, 1, applicant, token, 300, 1, 0, 0));
+		assert_eq!(MockAssets::reserved_balance(token, &applicant), 300);
+		assert_ok!(MolochV1::sponsor_proposal(Origin::signed(1), dao_id, 1));
+		run_to_period(dao_id, 6);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 1, 1));
+		run_to_period(dao_id, 10);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(3), dao_id, 1));
+		assert_eq!(MolochV1::members(dao_id, applicant).shares, 1);
+		assert_eq!(MolochV1::guild_bank_token_balance(dao_id, token), 300);
+		assert_eq!(MockAssets::balance(token, &applicant), 200);
+
+		// rage quitting half of the shares takes half of every whitelisted token
+		assert_ok!(MolochV1::ragequit(Origin::signed(applicant), dao_id, 1, 0));
+		assert_eq!(MockAssets::balance(token, &applicant), 350);
+		assert_eq!(MolochV1::guild_bank_token_balance(dao_id, token), 150);
+	});
+}
+
+#[test]
+fn batch_processing_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		summon_with(1);
+		let dao_id = 0;
+		for applicant in 2..4 {
+			assert_ok!(MolochV1::custody(Origin::signed(applicant), dao_id, 50));
+			assert_ok!(MolochV1::submit_proposal(Origin::signed(1), dao_id, applicant, 50, 1, 0, b"test_proposal".to_vec(), None));
+		}
+		assert_ok!(MolochV1::submit_whitelist_proposal(Origin::signed(1), dao_id, 1, b"whitelist".to_vec(), None));
+		for proposal_index in 0..3 {
+			run_to_period(dao_id, proposal_index + 1);
+			assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, proposal_index, 1));
+		}
+
+		// stops at the first proposal which is not ready, the reward is paid for each processed one
+		run_to_period(dao_id, 6);
+		let balance_before = Balances::free_balance(3);
+		// no more than `max` proposals, and only the weight of the processed ones is charged
+		let post_info = MolochV1::process_proposals(Origin::signed(3), dao_id, 5, 1, 0).unwrap();
+		assert_eq!(post_info.actual_weight, Some(10_000 + 10_000 + <Test as frame_system::Trait>::DbWeight::get().reads_writes(2,1)));
+		assert_eq!(MolochV1::processed_count(dao_id), 1);
+		assert_ok!(MolochV1::process_proposals(Origin::signed(3), dao_id, 5, 10, 0));
+		assert_eq!(MolochV1::processed_count(dao_id), 2);
+		assert_eq!(Balances::free_balance(3), balance_before + 100);
+		assert!(MolochV1::members(dao_id, 2).exists && MolochV1::members(dao_id, 3).exists);
+		assert_noop!(MolochV1::process_proposals(Origin::signed(3), dao_id, 5, 10, 0), Error::<Test>::ProposalNotReady);
+		assert_noop!(MolochV1::process_proposals(Origin::signed(3), dao_id, 1, 10, 0), Error::<Test>::ProposalHasProcessed);
+
+		run_to_period(dao_id, 7);
+		assert_ok!(MolochV1::process_proposals(Origin::signed(2), dao_id, 2, 10, 0));
+		assert_eq!(MolochV1::approved_tokens(dao_id), vec![1]);
+	});
+}
+
+#[test]
+fn funding_proposal_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		summon_with(1);
+		let dao_id = 0;
+		let grantee = 2;
+		let _ = Balances::deposit_creating(&MolochV1::account_id(dao_id), 500);
+
+		// no tribute and no shares, just a payment from the guild bank
+		assert_ok!(MolochV1::propose(Origin::signed(grantee), dao_id, 0, 0, 0, 300, b"grant".to_vec(), None));
+		assert_eq!(last_event(), RawEvent::Propose(dao_id, 0, grantee, 0, 0, 0, 300));
+		assert_ok!(MolochV1::sponsor_proposal(Origin::signed(1), dao_id, 0));
+		run_to_period(dao_id, 1);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 0, 1));
+		run_to_period(dao_id, 5);
+		let balance_before = Balances::free_balance(grantee);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(3), dao_id, 0));
+		assert!(System::events().iter().any(|r| r.event == Event::moloch_v1(RawEvent::PaymentTransferred(dao_id, 0, grantee, 300))));
+		assert_eq!(Balances::free_balance(grantee), balance_before + 300);
+
+		// the guild bank can not cover the payment any more, the proposal fails
+		assert_ok!(MolochV1::propose(Origin::signed(grantee), dao_id, 0, 0, 0, 1000, b"grant".to_vec(), None));
+		assert_ok!(MolochV1::sponsor_proposal(Origin::signed(1), dao_id, 1));
+		run_to_period(dao_id, 6);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 1, 1));
+		run_to_period(dao_id, 10);
+		let balance_before = Balances::free_balance(grantee);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(3), dao_id, 1));
+		assert!(System::events().iter().any(|r| r.event == Event::moloch_v1(RawEvent::GuildBankBalanceOutage(dao_id, 200, 1000))));
+		assert!(!MolochV1::proposals(dao_id, 1).did_pass);
+		assert_eq!(Balances::free_balance(grantee), balance_before);
+	});
+}
+
+parameter_types! {
+	pub const FirstDao: DaoId = 0;
+}
+
+#[test]
+fn action_proposal_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		summon_with(1);
+		let dao_id = 0;
+
+		// the call must fit in `MaxActionWeight`
+		let too_heavy = Box::new(Call::System(frame_system::Call::remark(vec![])));
+		assert_noop!(
+			MolochV1::submit_action_proposal(Origin::signed(1), dao_id, too_heavy, b"remark".to_vec(), None),
+			Error::<Test>::ActionTooHeavy
+		);
+
+		// set_storage needs root, so the call is refused to the guild's origin
+		let action = Box::new(Call::System(frame_system::Call::set_storage(vec![])));
+		assert_ok!(MolochV1::submit_action_proposal(Origin::signed(1), dao_id, action, b"set_storage".to_vec(), None));
+		assert_eq!(last_event(), RawEvent::SubmitActionProposal(dao_id, 0, 1, 1));
+		run_to_period(dao_id, 1);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 0, 1));
+		run_to_period(dao_id, 5);
+		// a batch only processes as many actions as it pays the weight for
+		assert_noop!(MolochV1::process_proposals(Origin::signed(3), dao_id, 0, 10, 0), Error::<Test>::ProposalNotReady);
+		assert_ok!(MolochV1::process_proposals(Origin::signed(3), dao_id, 0, 10, 1));
+		assert!(MolochV1::proposals(dao_id, 0).did_pass);
+		assert!(System::events().iter().any(|r| r.event == Event::moloch_v1(RawEvent::ActionExecuted(dao_id, 0, Err(DispatchError::BadOrigin)))));
+
+		// the bound on the weight is checked again when the call is dispatched
+		let action = Box::new(Call::System(frame_system::Call::set_storage(vec![])));
+		assert_ok!(MolochV1::submit_action_proposal(Origin::signed(1), dao_id, action, b"set_storage".to_vec(), None));
+		run_to_period(dao_id, 6);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 1, 1));
+		run_to_period(dao_id, 10);
+		MaxActionWeight::set(0);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(3), dao_id, 1));
+		assert!(System::events().iter().any(|r| matches!(r.event, Event::moloch_v1(RawEvent::ActionExecuted(0, 1, Err(DispatchError::Module { .. }))))));
+
+		// only the calls dispatched by the passed action proposals of the guild get through
+		assert_eq!(EnsureMolochMajority::<Origin, FirstDao>::try_origin(RawOrigin::Dao(0).into()).ok(), Some(0));
+		assert!(EnsureMolochMajority::<Origin, FirstDao>::try_origin(RawOrigin::Dao(1).into()).is_err());
+		assert!(EnsureMolochMajority::<Origin, FirstDao>::try_origin(Origin::signed(1)).is_err());
+	});
+}
+
+#[test]
+fn parameters_proposal_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		summon_with(1);
+		let dao_id = 0;
+
+		// the same bounds as summon
+		assert_noop!(
+			MolochV1::submit_parameters_proposal(Origin::signed(1), dao_id, MaxVotingPeriodLength::get() + 1, 1, 1, 1, 100, 50, vec![], b"too long".to_vec(), None),
+			Error::<Test>::VotingPeriodLengthTooBig
+		);
+		assert_noop!(
+			MolochV1::submit_parameters_proposal(Origin::signed(1), dao_id, 3, 1, 1, 1, 10, 50, vec![], b"no deposit".to_vec(), None),
+			Error::<Test>::NoEnoughProposalDeposit
+		);
+		let unbeatable = vec![(ProposalCategory::Action, VotingThreshold { quorum: 0, pass: 100 })];
+		assert_noop!(
+			MolochV1::submit_parameters_proposal(Origin::signed(1), dao_id, 3, 1, 2, 2, 200, 20, unbeatable, b"unbeatable".to_vec(), None),
+			Error::<Test>::InvalidThreshold
+		);
+		let twice = vec![(ProposalCategory::Action, VotingThreshold::default()), (ProposalCategory::Action, VotingThreshold::default())];
+		assert_noop!(
+			MolochV1::submit_parameters_proposal(Origin::signed(1), dao_id, 3, 1, 2, 2, 200, 20, twice, b"twice".to_vec(), None),
+			Error::<Test>::InvalidThreshold
+		);
+		assert_noop!(
+			MolochV1::submit_parameters_proposal(Origin::signed(2), dao_id, 3, 1, 2, 2, 200, 20, vec![], b"not member".to_vec(), None),
+			Error::<Test>::NotMember
+		);
+
+		let supermajority = VotingThreshold { quorum: 50, pass: 66 };
+		assert_ok!(MolochV1::submit_parameters_proposal(
+			Origin::signed(1), dao_id, 3, 1, 2, 2, 200, 20, vec![(ProposalCategory::Action, supermajority)], b"new settings".to_vec(), None
+		));
+		assert_eq!(last_event(), RawEvent::SubmitParametersProposal(dao_id, 0, 1, 1));
+		run_to_period(dao_id, 1);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 0, 1));
+		run_to_period(dao_id, 2);
+		assert_ok!(MolochV1::submit_whitelist_proposal(Origin::signed(1), dao_id, 1, b"whitelist".to_vec(), None));
+		run_to_period(dao_id, 5);
+		let processor_before = Balances::free_balance(3);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(3), dao_id, 0));
+		assert!(System::events().iter().any(|r| r.event == Event::moloch_v1(RawEvent::ParametersUpdated(dao_id, 0))));
+		assert_eq!(MolochV1::voting_period_length(dao_id), 3);
+		assert_eq!(MolochV1::grace_period_length(dao_id), 1);
+		assert_eq!(MolochV1::abort_window(dao_id), 2);
+		assert_eq!(MolochV1::dilution_bound(dao_id), 2);
+		assert_eq!(MolochV1::proposal_deposit(dao_id), 200);
+		assert_eq!(MolochV1::processing_reward(dao_id), 20);
+		assert_eq!(MolochV1::thresholds(dao_id, ProposalCategory::Action), supermajority);
+		assert_eq!(MolochV1::thresholds(dao_id, ProposalCategory::Membership), VotingThreshold::default());
+		// the proposal itself is still rewarded with the old settings
+		assert_eq!(Balances::free_balance(3), processor_before + 50);
+		// and the queued ones keep the periods they were voted with
+		assert_eq!(MolochV1::proposals(dao_id, 1).voting_period_length, 2);
+		assert_eq!(MolochV1::proposal_status(dao_id, 1), Some(ProposalStatus::Grace));
+
+		// summoning again creates another guild instead of overwriting the settings
+		summon_with(2);
+		assert_eq!(MolochV1::dao_count(), 2);
+		assert_eq!(MolochV1::voting_period_length(dao_id), 3);
+		assert_eq!(MolochV1::proposal_deposit(dao_id), 200);
+	});
+}
+
+#[test]
+fn voting_thresholds_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(MolochV1::summon_with(Origin::signed(0), 10, 2, 2, 1, 1, 100, 50, vec![(1, 6, 0), (2, 3, 0), (3, 1, 0)], 0));
+		let dao_id = 0;
+		let applicant = 0;
+		let supermajority = VotingThreshold { quorum: 50, pass: 66 };
+		assert_ok!(MolochV1::submit_parameters_proposal(
+			Origin::signed(1), dao_id, 2, 2, 1, 1, 100, 50, vec![(ProposalCategory::Membership, supermajority)], b"supermajority".to_vec(), None
+		));
+		run_to_period(dao_id, 1);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 0, 1));
+		run_to_period(dao_id, 5);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(3), dao_id, 0));
+		assert_eq!(MolochV1::thresholds(dao_id, ProposalCategory::Membership), supermajority);
+
+		// a single yes vote from a small member is not enough any more
+		assert_ok!(MolochV1::custody(Origin::signed(applicant), dao_id, 10));
+		assert_ok!(MolochV1::submit_proposal(Origin::signed(1), dao_id, applicant, 10, 1, 0, b"test_proposal".to_vec(), None));
+		run_to_period(dao_id, 6);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(3), dao_id, 1, 1));
+		run_to_period(dao_id, 10);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(3), dao_id, 1));
+		assert_eq!(last_event(), RawEvent::ProcessProposal(dao_id, 1, applicant, 1, 10, 1, 0, false, false, true));
+
+		// a quorum with a bare majority is not enough either
+		assert_ok!(MolochV1::custody(Origin::signed(applicant), dao_id, 10));
+		assert_ok!(MolochV1::submit_proposal(Origin::signed(1), dao_id, applicant, 10, 1, 0, b"test_proposal".to_vec(), None));
+		run_to_period(dao_id, 11);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 2, 1));
+		assert_ok!(MolochV1::submit_vote(Origin::signed(2), dao_id, 2, 2));
+		assert_ok!(MolochV1::submit_vote(Origin::signed(3), dao_id, 2, 2));
+		run_to_period(dao_id, 15);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(3), dao_id, 2));
+		assert_eq!(last_event(), RawEvent::ProcessProposal(dao_id, 2, applicant, 1, 10, 1, 0, false, true, false));
+		assert!(!MolochV1::proposals(dao_id, 2).did_pass);
+		assert_eq!(MolochV1::members(dao_id, applicant).shares, 0);
+	});
+}
+
+#[test]
+fn vote_changing_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(MolochV1::summon_with(Origin::signed(0), 10, 2, 2, 1, 1, 100, 50, vec![(1, 6, 0), (2, 3, 0), (3, 1, 0)], 0));
+		let dao_id = 0;
+		assert_ok!(MolochV1::submit_whitelist_proposal(Origin::signed(1), dao_id, 1, b"whitelist".to_vec(), None));
+		run_to_period(dao_id, 1);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(2), dao_id, 0, 1));
+		assert_noop!(MolochV1::submit_vote(Origin::signed(2), dao_id, 0, 1), Error::<Test>::MemberHasVoted);
+
+		// the tallies follow the new vote
+		assert_ok!(MolochV1::submit_vote(Origin::signed(2), dao_id, 0, 2));
+		assert_eq!(last_event(), RawEvent::VoteChanged(dao_id, 0, 2, 2, 2));
+		let proposal = MolochV1::proposals(dao_id, 0);
+		assert_eq!((proposal.yes_votes, proposal.no_votes), (0, 3));
+		assert_eq!(MolochV1::proposal_vote((dao_id, 0), 2), Ballot { vote: Vote::No, weight: 3, delegators: vec![] });
+
+		// only while the voting period lasts
+		run_to_period(dao_id, 3);
+		assert_noop!(MolochV1::submit_vote(Origin::signed(2), dao_id, 0, 1), Error::<Test>::ProposalExpired);
+	});
+}
+
+#[test]
+fn vote_delegation_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(MolochV1::summon_with(Origin::signed(0), 10, 2, 2, 1, 1, 100, 50, vec![(1, 6, 0), (2, 3, 0), (3, 1, 0)], 0));
+		let dao_id = 0;
+		assert_ok!(MolochV1::submit_whitelist_proposal(Origin::signed(1), dao_id, 1, b"whitelist".to_vec(), None));
+		assert_ok!(MolochV1::submit_whitelist_proposal(Origin::signed(1), dao_id, 2, b"whitelist".to_vec(), None));
+
+		assert_noop!(MolochV1::delegate_vote(Origin::signed(3), dao_id, 3, 3), Error::<Test>::InvalidDelegate);
+		assert_noop!(MolochV1::delegate_vote(Origin::signed(3), dao_id, 5, 3), Error::<Test>::InvalidDelegate);
+		assert_noop!(MolochV1::delegate_vote(Origin::signed(3), dao_id, 1, 0), Error::<Test>::InvalidDelegate);
+		assert_ok!(MolochV1::delegate_vote(Origin::signed(3), dao_id, 1, 3));
+		assert_eq!(last_event(), RawEvent::VoteDelegated(dao_id, 3, 1, 3));
+		assert_eq!(MolochV1::delegators(dao_id, 1), vec![3]);
+		// delegations are not transitive
+		assert_noop!(MolochV1::delegate_vote(Origin::signed(2), dao_id, 3, 3), Error::<Test>::InvalidDelegate);
+
+		// the delegated shares are tallied with the vote of the delegate
+		run_to_period(dao_id, 2);
+		assert_noop!(MolochV1::submit_vote(Origin::signed(3), dao_id, 1, 1), Error::<Test>::VoteDelegated);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 1, 1));
+		assert_eq!(MolochV1::proposals(dao_id, 1).yes_votes, 7);
+		assert_eq!(MolochV1::proposal_vote((dao_id, 1), 1), Ballot { vote: Vote::Yes, weight: 7, delegators: vec![3] });
+		assert_eq!(MolochV1::members(dao_id, 3).highest_index_yes_vote, Some(1));
+
+		// taking the delegation back does not give a second say on the proposal
+		assert_ok!(MolochV1::undelegate_vote(Origin::signed(3), dao_id));
+		assert_eq!(MolochV1::delegators(dao_id, 1), Vec::<u128>::new());
+		assert_noop!(MolochV1::submit_vote(Origin::signed(3), dao_id, 1, 2), Error::<Test>::VoteDelegated);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(3), dao_id, 0, 2));
+		assert_eq!(MolochV1::proposals(dao_id, 0).no_votes, 1);
+		// a changed vote keeps its weight
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 1, 2));
+		let proposal = MolochV1::proposals(dao_id, 1);
+		assert_eq!((proposal.yes_votes, proposal.no_votes), (0, 7));
+
+		// expired delegations are not tallied
+		assert_ok!(MolochV1::delegate_vote(Origin::signed(2), dao_id, 1, 1));
+		assert_ok!(MolochV1::submit_whitelist_proposal(Origin::signed(1), dao_id, 3, b"whitelist".to_vec(), None));
+		run_to_period(dao_id, 3);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 2, 1));
+		assert_eq!(MolochV1::proposals(dao_id, 2).yes_votes, 6);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(2), dao_id, 2, 1));
+		assert_eq!(MolochV1::proposals(dao_id, 2).yes_votes, 9);
+	});
+}
+
+#[test]
+fn delegations_do_not_chain() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(MolochV1::summon_with(Origin::signed(0), 10, 2, 2, 1, 1, 100, 50, vec![(1, 6, 0), (2, 3, 0), (3, 1, 0)], 0));
+		let dao_id = 0;
+		// 3 delegates to 1, who can not pass the shares of 3 on to 2
+		assert_ok!(MolochV1::delegate_vote(Origin::signed(3), dao_id, 1, 2));
+		assert_noop!(MolochV1::delegate_vote(Origin::signed(1), dao_id, 2, 2), Error::<Test>::InvalidDelegate);
+
+		// once the delegation of 3 has expired, 1 is free to delegate
+		run_to_period(dao_id, 2);
+		assert_ok!(MolochV1::delegate_vote(Origin::signed(1), dao_id, 2, 2));
+		assert_eq!(MolochV1::delegators(dao_id, 2), vec![1]);
+	});
+}
+
+#[test]
+fn expired_delegations_are_pruned() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(MolochV1::summon_with(Origin::signed(0), 10, 2, 2, 1, 1, 100, 50, vec![(0, 1, 0), (1, 6, 0), (2, 3, 0), (3, 1, 0)], 0));
+		let dao_id = 0;
+		assert_ok!(MolochV1::delegate_vote(Origin::signed(2), dao_id, 1, 1));
+		assert_ok!(MolochV1::delegate_vote(Origin::signed(3), dao_id, 1, 1));
+		assert_noop!(MolochV1::delegate_vote(Origin::signed(0), dao_id, 1, 1), Error::<Test>::TooManyDelegators);
+
+		// the expired delegations make room for new ones
+		run_to_period(dao_id, 1);
+		assert_ok!(MolochV1::delegate_vote(Origin::signed(0), dao_id, 1, 1));
+		assert_eq!(MolochV1::delegators(dao_id, 1), vec![0]);
 	});
-}
\ No newline at end of file
+}
+
+#[test]
+fn late_delegations_keep_their_vote() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(MolochV1::summon_with(Origin::signed(0), 10, 2, 2, 1, 1, 100, 50, vec![(1, 6, 0), (2, 3, 0), (3, 1, 0)], 0));
+		let dao_id = 0;
+		assert_ok!(MolochV1::submit_whitelist_proposal(Origin::signed(1), dao_id, 1, b"whitelist".to_vec(), None));
+		assert_ok!(MolochV1::submit_whitelist_proposal(Origin::signed(1), dao_id, 2, b"whitelist".to_vec(), None));
+		run_to_period(dao_id, 1);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 0, 1));
+
+		// the ballot of 1 on the first proposal was cast without the shares of 2
+		assert_ok!(MolochV1::delegate_vote(Origin::signed(2), dao_id, 1, 5));
+		assert_ok!(MolochV1::submit_vote(Origin::signed(2), dao_id, 0, 2));
+		assert_eq!(MolochV1::proposals(dao_id, 0).no_votes, 3);
+		// on the next one, the delegate votes for them
+		run_to_period(dao_id, 2);
+		assert_noop!(MolochV1::submit_vote(Origin::signed(2), dao_id, 1, 2), Error::<Test>::VoteDelegated);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 1, 1));
+		assert_eq!(MolochV1::proposals(dao_id, 1).yes_votes, 9);
+	});
+}
+
+#[test]
+fn ragequit_shares_do_not_vote() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(MolochV1::summon_with(Origin::signed(0), 10, 2, 2, 1, 1, 100, 50, vec![(1, 6, 0), (2, 3, 0), (3, 2, 0)], 0));
+		let dao_id = 0;
+		assert_ok!(MolochV1::submit_whitelist_proposal(Origin::signed(1), dao_id, 1, b"whitelist".to_vec(), None));
+		run_to_period(dao_id, 1);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 0, 1));
+		run_to_period(dao_id, 5);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(1), dao_id, 0));
+		assert_ok!(MolochV1::delegate_vote(Origin::signed(3), dao_id, 1, 5));
+		assert_ok!(MolochV1::submit_whitelist_proposal(Origin::signed(1), dao_id, 2, b"whitelist".to_vec(), None));
+
+		// the shares cashed out during the voting period are gone from the ballots, delegated or not
+		run_to_period(dao_id, 6);
+		assert_ok!(MolochV1::ragequit(Origin::signed(2), dao_id, 2, 0));
+		assert_ok!(MolochV1::ragequit(Origin::signed(3), dao_id, 1, 0));
+		assert_ok!(MolochV1::submit_vote(Origin::signed(2), dao_id, 1, 1));
+		assert_eq!(MolochV1::proposals(dao_id, 1).yes_votes, 1);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 1, 2));
+		assert_eq!(MolochV1::proposals(dao_id, 1).no_votes, 7);
+	});
+}
+
+#[test]
+fn migrate_votes_to_ballots_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		summon_with(1);
+		// the raw vote units stored before `Releases::V5`
+		unhashed::put(&ProposalVotes::<Test>::hashed_key_for((0, 0), 1), &1u8);
+		unhashed::put(&ProposalVotes::<Test>::hashed_key_for((0, 1), 1), &0u8);
+		StorageVersion::put(Releases::V4);
+
+		MolochV1::on_runtime_upgrade();
+
+		assert_eq!(MolochV1::storage_version(), Releases::V6);
+		assert_eq!(MolochV1::proposal_vote((0, 0), 1), Ballot { vote: Vote::Yes, weight: 1, delegators: vec![] });
+		assert!(!ProposalVotes::<Test>::contains_key((0, 1), 1));
+	});
+}
+
+#[test]
+fn share_snapshots_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(MolochV1::summon_with(Origin::signed(0), 10, 2, 2, 1, 1, 100, 50, vec![(1, 6, 0), (2, 3, 0), (3, 1, 0)], 0));
+		let dao_id = 0;
+		assert_eq!(MolochV1::share_checkpoints(dao_id, 3), vec![(0, 1)]);
+		assert_ok!(MolochV1::custody(Origin::signed(3), dao_id, 10));
+		assert_ok!(MolochV1::submit_proposal(Origin::signed(1), dao_id, 3, 10, 10, 0, b"more shares".to_vec(), None));
+		run_to_period(dao_id, 1);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 0, 1));
+		run_to_period(dao_id, 4);
+		assert_ok!(MolochV1::submit_whitelist_proposal(Origin::signed(1), dao_id, 1, b"whitelist".to_vec(), None));
+		assert_eq!(MolochV1::proposals(dao_id, 1).starting_period, 5);
+
+		// the shares minted when voting has started do not count
+		run_to_period(dao_id, 5);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(1), dao_id, 0));
+		assert_eq!(MolochV1::members(dao_id, 3).shares, 11);
+		assert_eq!(MolochV1::share_checkpoints(dao_id, 3), vec![(0, 1), (5, 11)]);
+		assert_eq!(MolochV1::total_share_checkpoints(dao_id), vec![(0, 10), (5, 20)]);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(3), dao_id, 1, 1));
+		assert_eq!(MolochV1::proposals(dao_id, 1).yes_votes, 1);
+		// they do on the proposals starting later
+		assert_ok!(MolochV1::submit_whitelist_proposal(Origin::signed(1), dao_id, 2, b"whitelist".to_vec(), None));
+		run_to_period(dao_id, 6);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(3), dao_id, 2, 1));
+		assert_eq!(MolochV1::proposals(dao_id, 2).yes_votes, 11);
+
+		// members holding no shares when voting started can not vote
+		assert_ok!(MolochV1::custody(Origin::signed(0), dao_id, 10));
+		assert_ok!(MolochV1::submit_proposal(Origin::signed(1), dao_id, 0, 10, 1, 0, b"new member".to_vec(), None));
+		run_to_period(dao_id, 7);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 3, 1));
+		run_to_period(dao_id, 9);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(1), dao_id, 1));
+		run_to_period(dao_id, 10);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(1), dao_id, 2));
+		assert_ok!(MolochV1::submit_guild_kick(Origin::signed(1), dao_id, 2, b"kick".to_vec(), None));
+		run_to_period(dao_id, 11);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(1), dao_id, 3));
+		assert_noop!(MolochV1::submit_vote(Origin::signed(0), dao_id, 4, 1), Error::<Test>::NoEnoughShares);
+
+		// the history nothing can look up any more is dropped
+		assert_ok!(MolochV1::ragequit(Origin::signed(3), dao_id, 1, 0));
+		assert_eq!(MolochV1::share_checkpoints(dao_id, 3), vec![(5, 11), (11, 10)]);
+	});
+}
+
+#[test]
+fn migrate_share_checkpoints_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		summon_with(1);
+		ShareCheckpoints::<Test>::remove(0, 1);
+		TotalShareCheckpoints::remove(0);
+		StorageVersion::put(Releases::V5);
+
+		MolochV1::on_runtime_upgrade();
+
+		assert_eq!(MolochV1::storage_version(), Releases::V6);
+		assert_eq!(MolochV1::share_checkpoints(0, 1), vec![(0, 1)]);
+		assert_eq!(MolochV1::total_share_checkpoints(0), vec![(0, 1)]);
+	});
+}
+
+#[test]
+fn summon_with_founders_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// someone must hold voting shares, and every founder only once
+		assert_noop!(
+			MolochV1::summon_with(Origin::signed(0), 10, 2, 2, 1, 1, 100, 50, vec![(1, 0, 10)], 0),
+			Error::<Test>::NoEnoughShares
+		);
+		assert_noop!(
+			MolochV1::summon_with(Origin::signed(0), 10, 2, 2, 1, 1, 100, 50, vec![(1, 5, 0), (1, 5, 0)], 0),
+			Error::<Test>::NoOverwriteMember
+		);
+
+		assert_ok!(MolochV1::summon_with(Origin::signed(0), 10, 2, 2, 1, 1, 100, 50, vec![(1, 10, 0), (2, 5, 5)], 500));
+		let dao_id = 0;
+		assert_eq!(last_event(), RawEvent::SummonComplete(dao_id, 2, 5));
+		assert_eq!(MolochV1::member_info(dao_id, 1), Some((10, 0, None, 1)));
+		assert_eq!(MolochV1::member_info(dao_id, 2), Some((5, 5, None, 2)));
+		// the summoner is not a founder
+		assert_eq!(MolochV1::member_info(dao_id, 0), None);
+		assert_eq!(MolochV1::totoal_shares(dao_id), 15);
+		assert_eq!(MolochV1::total_loot(dao_id), 5);
+		// the funding comes on top of the existential deposit
+		assert_eq!(MolochV1::guild_bank_balance(dao_id), 501);
+	});
+}
+
+#[test]
+fn genesis_guilds_work() {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	crate::GenesisConfig::<Test> {
+		guilds: vec![GuildGenesis {
+			period_duration: 10,
+			parameters: GuildParameters {
+				voting_period_length: 2,
+				grace_period_length: 2,
+				abort_window: 1,
+				dilution_bound: 1,
+				proposal_deposit: 100,
+				processing_reward: 50,
+				thresholds: vec![(ProposalCategory::Action, VotingThreshold { quorum: 50, pass: 66 })],
+			},
+			founders: vec![(1, 10, 0), (2, 5, 5)],
+			funding: 1000,
+		}],
+	}.assimilate_storage(&mut t).unwrap();
+	sp_io::TestExternalities::from(t).execute_with(|| {
+		let dao_id = 0;
+		assert_eq!(MolochV1::dao_count(), 1);
+		assert_eq!(MolochV1::voting_period_length(dao_id), 2);
+		assert_eq!(MolochV1::proposal_deposit(dao_id), 100);
+		assert_eq!(MolochV1::member_info(dao_id, 2), Some((5, 5, None, 2)));
+		assert_eq!(MolochV1::thresholds(dao_id, ProposalCategory::Action), VotingThreshold { quorum: 50, pass: 66 });
+		assert_eq!(MolochV1::guild_bank_balance(dao_id), 1001);
+		assert_eq!(MolochV1::storage_version(), Releases::V6);
+		// founders can rage quit before any proposal exists
+		assert_ok!(MolochV1::ragequit(Origin::signed(2), dao_id, 0, 5));
+		assert_eq!(MolochV1::member_info(dao_id, 2), Some((5, 0, None, 2)));
+	});
+}
+
+#[test]
+fn arithmetic_edges_are_rejected() {
+	new_test_ext().execute_with(|| {
+		// periods of zero blocks would never end
+		assert_noop!(
+			MolochV1::summon(Origin::signed(1), 0, 2, 2, 1, 1, 100, 50),
+			Error::<Test>::InvalidPeriodDuration
+		);
+		System::set_block_number(5);
+		summon_with(1);
+		let dao_id = 0;
+
+		// the clock is behind the summon time
+		System::set_block_number(4);
+		assert_eq!(MolochV1::get_current_period(dao_id), Err(Error::<Test>::PeriodNotReached.into()));
+		assert_eq!(MolochV1::current_period(dao_id), None);
+		System::set_block_number(5);
+
+		// the shares requested do not fit in u128
+		let applicant = 2;
+		assert_ok!(MolochV1::custody(Origin::signed(applicant), dao_id, 50));
+		assert_noop!(
+			MolochV1::submit_proposal(Origin::signed(1), dao_id, applicant, 50, u128::max_value(), 0, b"test_proposal".to_vec(), None),
+			Error::<Test>::SharesOverFlow
+		);
+
+		// the proposal can not be processed before the grace period ends
+		assert_ok!(MolochV1::submit_proposal(Origin::signed(1), dao_id, applicant, 50, 5, 0, b"test_proposal".to_vec(), None));
+		assert_noop!(MolochV1::process_proposal(Origin::signed(3), dao_id, 0), Error::<Test>::ProposalNotReady);
+
+		// nothing to burn
+		assert_noop!(MolochV1::ragequit(Origin::signed(1), dao_id, 0, 0), Error::<Test>::NoEnoughShares);
+
+		// the mock's balance is a u64
+		assert_eq!(MolochV1::u128_to_balance(100), Ok(100));
+		assert_eq!(MolochV1::u128_to_balance(u128::max_value()), Err(Error::<Test>::BalanceConversion.into()));
+	});
+}
+
+#[test]
+fn underfunded_transfers_change_nothing() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// the funding would take the last of the founder's balance
+		assert_noop!(
+			MolochV1::summon_with(Origin::signed(POOR), 10, 2, 2, 1, 1, 100, 50, vec![(POOR, 1, 0)], 9),
+			BalancesError::<Test, DefaultInstance>::KeepAlive
+		);
+		assert_ok!(MolochV1::summon_with(Origin::signed(1), 10, 2, 2, 1, 1, 100, 50, vec![(1, 1, 0), (POOR, 1, 0)], 0));
+		let dao_id = 0;
+
+		// the tribute can not be reserved
+		assert_noop!(
+			MolochV1::custody(Origin::signed(POOR), dao_id, 50),
+			BalancesError::<Test, DefaultInstance>::InsufficientBalance
+		);
+		assert_noop!(
+			MolochV1::propose(Origin::signed(POOR), dao_id, 50, 1, 0, 0, b"test_proposal".to_vec(), None),
+			BalancesError::<Test, DefaultInstance>::InsufficientBalance
+		);
+
+		// the member can not pay the proposal deposit
+		let applicant = 2;
+		assert_ok!(MolochV1::custody(Origin::signed(applicant), dao_id, 50));
+		assert_noop!(
+			MolochV1::submit_proposal(Origin::signed(POOR), dao_id, applicant, 50, 5, 0, b"test_proposal".to_vec(), None),
+			BalancesError::<Test, DefaultInstance>::InsufficientBalance
+		);
+		assert_eq!(MolochV1::totoal_shares_requested(dao_id), 0);
+	});
+}
+
+#[test]
+fn deposit_and_tribute_are_reserved() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		summon_with(1);
+		let dao_id = 0;
+		let applicant = 2;
+		let processor = 3;
+		// the tribute and the deposit stay on their owners' accounts until processing
+		assert_ok!(MolochV1::custody(Origin::signed(applicant), dao_id, 50));
+		assert_ok!(MolochV1::submit_proposal(Origin::signed(1), dao_id, applicant, 50, 5, 0, b"test_proposal".to_vec(), None));
+		assert_eq!(Balances::reserved_balance(applicant), 50);
+		assert_eq!(Balances::reserved_balance(1), 100);
+		assert_eq!(MolochV1::proposals(dao_id, 0).depositor, 1);
+		assert_eq!(MolochV1::guild_bank_balance(dao_id), 1);
+
+		run_to_period(dao_id, 1);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 0, 1));
+		run_to_period(dao_id, 5);
+		let sponsor_before = Balances::free_balance(1);
+		let processor_before = Balances::free_balance(processor);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(processor), dao_id, 0));
+		// the tribute lands in the guild bank, the reward comes out of the deposit
+		assert_eq!(Balances::reserved_balance(applicant), 0);
+		assert_eq!(MolochV1::guild_bank_balance(dao_id), 51);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), sponsor_before + 50);
+		assert_eq!(Balances::free_balance(processor), processor_before + 50);
+
+		// the tribute of a failed proposal is unreserved
+		assert_ok!(MolochV1::custody(Origin::signed(processor), dao_id, 10));
+		assert_ok!(MolochV1::submit_proposal(Origin::signed(1), dao_id, processor, 10, 1, 0, b"test_proposal".to_vec(), None));
+		assert_eq!(Balances::reserved_balance(processor), 10);
+		run_to_period(dao_id, 10);
+		let processor_before = Balances::free_balance(processor);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(applicant), dao_id, 1));
+		assert!(!MolochV1::proposals(dao_id, 1).did_pass);
+		assert_eq!(Balances::reserved_balance(processor), 0);
+		assert_eq!(Balances::free_balance(processor), processor_before + 10);
+	});
+}
+
+#[test]
+fn ragequit_pays_out_the_guild_bank_only() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(MolochV1::summon_with(Origin::signed(0), 10, 2, 2, 1, 1, 100, 50, vec![(1, 6, 0), (2, 4, 0)], 500));
+		let dao_id = 0;
+		assert_ok!(MolochV1::submit_whitelist_proposal(Origin::signed(1), dao_id, 1, b"whitelist".to_vec(), None));
+		run_to_period(dao_id, 1);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 0, 1));
+		run_to_period(dao_id, 5);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(1), dao_id, 0));
+
+		// a queued proposal and some unused custody
+		assert_ok!(MolochV1::custody(Origin::signed(3), dao_id, 50));
+		assert_ok!(MolochV1::submit_proposal(Origin::signed(1), dao_id, 3, 50, 5, 0, b"test_proposal".to_vec(), None));
+		assert_ok!(MolochV1::custody(Origin::signed(0), dao_id, 10));
+		assert_eq!(MolochV1::guild_bank_balance(dao_id), 501);
+		assert_eq!(MolochV1::distributable_balance(dao_id), 500);
+
+		// neither of them is paid out
+		assert_eq!(MolochV1::ragequit_preview(dao_id, 4), 200);
+		let balance_before = Balances::free_balance(2);
+		assert_ok!(MolochV1::ragequit(Origin::signed(2), dao_id, 4, 0));
+		assert_eq!(Balances::free_balance(2), balance_before + 200);
+		// the last shares take the whole bank but its existential deposit
+		assert_eq!(MolochV1::ragequit_preview(dao_id, 6), 300);
+		assert_ok!(MolochV1::ragequit(Origin::signed(1), dao_id, 6, 0));
+		assert_eq!(MolochV1::guild_bank_balance(dao_id), 1);
+		assert_eq!(Balances::reserved_balance(1), 100);
+		assert_eq!(Balances::reserved_balance(3), 50);
+		assert_eq!(Balances::reserved_balance(0), 10);
+	});
+}
+
+#[test]
+fn concurrent_applicants_have_separate_escrow() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		summon_with(1);
+		let dao_id = 0;
+		let applicant = 2;
+		let other_applicant = 3;
+		assert_ok!(MolochV1::custody(Origin::signed(applicant), dao_id, 50));
+		assert_ok!(MolochV1::custody(Origin::signed(other_applicant), dao_id, 30));
+
+		// a tribute can only back one proposal, and only the applicant's own custody counts
+		assert_ok!(MolochV1::submit_proposal(Origin::signed(1), dao_id, applicant, 50, 5, 0, b"test_proposal".to_vec(), None));
+		assert_eq!(MolochV1::applicant_custody(dao_id, applicant), 0);
+		assert_noop!(
+			MolochV1::submit_proposal(Origin::signed(1), dao_id, applicant, 50, 5, 0, b"test_proposal".to_vec(), None),
+			Error::<Test>::NoCustodyFound
+		);
+		assert_noop!(
+			MolochV1::submit_proposal(Origin::signed(1), dao_id, other_applicant, 50, 5, 0, b"test_proposal".to_vec(), None),
+			Error::<Test>::NoCustodyFound
+		);
+
+		// custody adds up instead of being overwritten
+		assert_ok!(MolochV1::custody(Origin::signed(other_applicant), dao_id, 20));
+		assert_eq!(MolochV1::applicant_custody(dao_id, other_applicant), 50);
+		assert_eq!(Balances::reserved_balance(other_applicant), 50);
+		assert_ok!(MolochV1::submit_proposal(Origin::signed(1), dao_id, other_applicant, 50, 5, 0, b"test_proposal".to_vec(), None));
+
+		// only the custody not locked by a proposal can be withdrawn
+		assert_ok!(MolochV1::custody(Origin::signed(applicant), dao_id, 10));
+		assert_noop!(MolochV1::withdraw_custody(Origin::signed(applicant), dao_id, 20), Error::<Test>::NoCustodyFound);
+		assert_ok!(MolochV1::withdraw_custody(Origin::signed(applicant), dao_id, 10));
+		assert_eq!(last_event(), RawEvent::CustodyWithdrawn(dao_id, applicant, 10));
+		assert!(!ApplicantCustody::<Test>::contains_key(dao_id, applicant));
+		assert_eq!(Balances::reserved_balance(applicant), 50);
+
+		// each tribute follows its own proposal
+		run_to_period(dao_id, 1);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 0, 1));
+		run_to_period(dao_id, 2);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 1, 2));
+		run_to_period(dao_id, 5);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(1), dao_id, 0));
+		assert_eq!(Balances::reserved_balance(applicant), 0);
+		assert_eq!(Balances::reserved_balance(other_applicant), 50);
+		assert_eq!(MolochV1::guild_bank_balance(dao_id), 51);
+		run_to_period(dao_id, 6);
+		let balance_before = Balances::free_balance(other_applicant);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(1), dao_id, 1));
+		assert_eq!(Balances::reserved_balance(other_applicant), 0);
+		assert_eq!(Balances::free_balance(other_applicant), balance_before + 50);
+		assert_eq!(MolochV1::guild_bank_balance(dao_id), 51);
+	});
+}
+
+#[test]
+fn slashed_custody_is_an_outage() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		summon_with(1);
+		let dao_id = 0;
+		let applicant = 2;
+		assert_ok!(MolochV1::custody(Origin::signed(applicant), dao_id, 100));
+		assert_ok!(MolochV1::submit_proposal(Origin::signed(1), dao_id, applicant, 50, 5, 0, b"test_proposal".to_vec(), None));
+		assert_eq!(MolochV1::locked_tribute((dao_id, None), applicant), 50);
+		// the applicant also holds custody in another guild
+		summon_with(3);
+		assert_ok!(MolochV1::custody(Origin::signed(applicant), 1, 100));
+		assert_eq!(MolochV1::held_tributes(applicant), 200);
+
+		// what is left reserved would cover this guild, but not the other one on top of it
+		let _ = Balances::slash_reserved(&applicant, 50);
+		assert_noop!(MolochV1::withdraw_custody(Origin::signed(applicant), dao_id, 50), Error::<Test>::TributeNotReserved);
+		run_to_period(dao_id, 1);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 0, 1));
+		run_to_period(dao_id, 5);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(3), dao_id, 0));
+		assert!(System::events().iter().any(|r| r.event == Event::moloch_v1(RawEvent::CustodyBalanceOutage(dao_id, 150, 200))));
+		assert!(!MolochV1::proposals(dao_id, 0).did_pass);
+		assert_eq!(MolochV1::locked_tribute((dao_id, None), applicant), 0);
+		assert_eq!(MolochV1::held_tributes(applicant), 150);
+	});
+}
+
+#[test]
+fn auto_processing_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		summon_with(1);
+		let dao_id = 0;
+		for token in 1..4 {
+			assert_ok!(MolochV1::submit_whitelist_proposal(Origin::signed(1), dao_id, token, b"whitelist".to_vec(), None));
+		}
+		let sponsor_before = Balances::free_balance(1);
+		run_to_period(dao_id, 4);
+		MolochV1::on_initialize(System::block_number());
+		assert_eq!(MolochV1::processed_count(dao_id), 0);
+
+		// two proposals fit in `AutoProcessWeight`, the third one waits for the next block
+		run_to_period(dao_id, 7);
+		assert!(MolochV1::on_initialize(System::block_number()) <= AutoProcessWeight::get());
+		assert_eq!(MolochV1::processed_count(dao_id), 2);
+		System::set_block_number(System::block_number() + 1);
+		MolochV1::on_initialize(System::block_number());
+		assert_eq!(MolochV1::processed_count(dao_id), 3);
+		assert_eq!(last_event(), RawEvent::ProcessProposal(dao_id, 2, 1, 1, 0, 0, 0, false, true, false));
+		// nobody earns the processing reward, the deposits are returned in full
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), sponsor_before + 300);
+	});
+}
+
+#[test]
+fn failed_auto_processing_waits_for_process_proposal() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		summon_with(1);
+		let dao_id = 0;
+		let applicant = 2;
+		assert_ok!(MolochV1::custody(Origin::signed(applicant), dao_id, 50));
+		assert_ok!(MolochV1::submit_proposal(Origin::signed(1), dao_id, applicant, 50, 5, 0, b"test_proposal".to_vec(), None));
+		run_to_period(dao_id, 1);
+		assert_ok!(MolochV1::submit_vote(Origin::signed(1), dao_id, 0, 1));
+		// break the bookkeeping so that processing fails
+		TotalSharesRequested::insert(dao_id, 0);
+		run_to_period(dao_id, 5);
+		MolochV1::on_initialize(System::block_number());
+		assert!(matches!(last_event(), RawEvent::AutoProcessFailed(0, 0, DispatchError::Module { .. })));
+		assert_eq!(MolochV1::stuck_proposal(dao_id), Some(0));
+		// the failed attempt is rolled back
+		assert_eq!(MolochV1::processed_count(dao_id), 0);
+		assert!(!MolochV1::proposals(dao_id, 0).processed);
+		assert_eq!(Balances::reserved_balance(applicant), 50);
+
+		// neither the hook nor the off-chain worker try again
+		let events = System::events().len();
+		System::set_block_number(System::block_number() + 1);
+		MolochV1::on_initialize(System::block_number());
+		assert_eq!(System::events().len(), events);
+		let call = crate::Call::process_proposal_unsigned(dao_id, 0);
+		assert_eq!(MolochV1::validate_unsigned(TransactionSource::External, &call), InvalidTransaction::Call.into());
+		TotalSharesRequested::insert(dao_id, 5);
+		assert_ok!(MolochV1::process_proposal(Origin::signed(3), dao_id, 0));
+		assert_eq!(MolochV1::members(dao_id, applicant).shares, 5);
+		assert_eq!(MolochV1::stuck_proposal(dao_id), None);
+	});
+}
+
+#[test]
+fn offchain_worker_processes_ready_proposals() {
+	let (offchain, _) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let mut t = new_test_ext();
+	t.register_extension(OffchainExt::new(offchain));
+	t.register_extension(TransactionPoolExt::new(pool));
+	t.execute_with(|| {
+		System::set_block_number(1);
+		summon_with(1);
+		let dao_id = 0;
+		assert_ok!(MolochV1::submit_whitelist_proposal(Origin::signed(1), dao_id, 1, b"whitelist".to_vec(), None));
+		let call = crate::Call::process_proposal_unsigned(dao_id, 0);
+
+		// nothing to submit before the proposal is ready
+		run_to_period(dao_id, 4);
+		MolochV1::offchain_worker(System::block_number());
+		assert!(pool_state.read().transactions.is_empty());
+		assert_eq!(MolochV1::validate_unsigned(TransactionSource::External, &call), InvalidTransaction::Stale.into());
+
+		run_to_period(dao_id, 5);
+		MolochV1::offchain_worker(System::block_number());
+		let tx = pool_state.write().transactions.pop().unwrap();
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature, None);
+		assert_eq!(tx.call, call);
+		assert!(MolochV1::validate_unsigned(TransactionSource::External, &call).is_ok());
+		assert_eq!(
+			MolochV1::validate_unsigned(TransactionSource::External, &crate::Call::process_proposal_unsigned(dao_id, 1)),
+			InvalidTransaction::Stale.into()
+		);
+
+		assert_noop!(MolochV1::process_proposal_unsigned(Origin::signed(1), dao_id, 0), BadOrigin);
+		assert_ok!(MolochV1::process_proposal_unsigned(Origin::none(), dao_id, 0));
+		assert_eq!(MolochV1::processed_count(dao_id), 1);
+		assert_eq!(MolochV1::validate_unsigned(TransactionSource::External, &call), InvalidTransaction::Stale.into());
+	});
+}
+
+#[test]
+fn migrate_custody_to_reserves_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		summon_with(1);
+		let dao_id = 0;
+		let applicant = 2;
+		let guild_bank = MolochV1::account_id(dao_id);
+		let bank_before = Balances::free_balance(guild_bank);
+		// the tributes used to sit in the custody account, including the one of a queued proposal
+		let custody_account = MolochV1::legacy_custody_account();
+		assert_ok!(Balances::transfer(Origin::signed(applicant), custody_account, 80));
+		ApplicantCustody::<Test>::insert(dao_id, applicant, 80);
+		// and the deposit of its sponsor in the guild bank
+		assert_ok!(Balances::transfer(Origin::signed(1), guild_bank, 100));
+		let proposal = Proposal { proposer: 1, applicant: applicant, token_tribute: 50, deposit: 100, depositor: 1, ..Default::default() };
+		Proposals::<Test>::insert(dao_id, 0, proposal);
+		ProposalCount::insert(dao_id, 1);
+		StorageVersion::put(Releases::V3);
+
+		MolochV1::on_runtime_upgrade();
+
+		assert_eq!(MolochV1::storage_version(), Releases::V6);
+		assert_eq!(Balances::free_balance(custody_account), 0);
+		assert_eq!(Balances::reserved_balance(applicant), 80);
+		// the tribute of the queued proposal is locked, not withdrawable custody
+		assert_eq!(MolochV1::applicant_custody(dao_id, applicant), 30);
+		assert_eq!(MolochV1::locked_tribute((dao_id, None), applicant), 50);
+		assert_eq!(MolochV1::held_tributes(applicant), 80);
+		// ragequitters can not take the deposit, it is reserved on the sponsor
+		assert_eq!(Balances::free_balance(guild_bank), bank_before);
+		assert_eq!(Balances::reserved_balance(1), 100);
+		assert_eq!(MolochV1::proposals(dao_id, 0).deposit, 100);
+	});
+}
@@ -6,10 +6,10 @@
 /// debug guide https://substrate.dev/recipes/runtime-printing.html
 
 use frame_support::{
-	decl_module, decl_storage, decl_event, decl_error, dispatch, debug, ensure,
+	decl_module, decl_storage, decl_event, decl_error, dispatch, debug, ensure, transactional,
 	traits::{Currency, EnsureOrigin, ReservableCurrency, OnUnbalanced, Get, ExistenceRequirement::{KeepAlive}},
 };
-use sp_runtime::{ModuleId, traits::{ Hash, AccountIdConversion}};
+use sp_runtime::{ModuleId, DispatchError, traits::{ Hash, AccountIdConversion, SaturatedConversion}};
 use frame_support::codec::{Encode, Decode};
 use frame_system::{ensure_signed};
 use sp_std::{vec, vec::Vec, convert::{TryInto}};
@@ -149,6 +149,14 @@ decl_error! {
 		RoundNotExist,
 		RoundHasEnded,
 		DuplicateRound,
+		/// An arithmetic operation overflowed
+		Overflow,
+		/// An arithmetic operation went below zero
+		Underflow,
+		/// The amount does not fit in the balance type of the runtime
+		BalanceConversion,
+		/// A division by zero, the number of units per vote is configured to zero
+		DivisionByZero,
 	}
 }
 
@@ -176,21 +184,23 @@ decl_module! {
 			let round = Rounds::get(round_id);
 			ensure!(true == round.ongoing, Error::<T>::RoundHasEnded);
 			// the minimum unit, make sure the donate is greater than this
-			let min_unit_number = Self::cal_amount(1u128, false);
-			let amount_number = Self::balance_to_u128(amount);
-			let fee_number = T::FeeRatioPerVote::get().checked_mul(amount_number / T::NumberOfUnitPerVote::get()).unwrap();
+			let min_unit_number = Self::cal_amount(1u128, false)?;
+			let amount_number = Self::balance_to_u128(amount)?;
+			let votes_number = amount_number.checked_div(T::NumberOfUnitPerVote::get()).ok_or(Error::<T>::DivisionByZero)?;
+			let fee_number = T::FeeRatioPerVote::get().checked_mul(votes_number).ok_or(Error::<T>::Overflow)?;
 			ensure!(amount_number > min_unit_number, Error::<T>::DonationTooSmall);
-			let _ = T::Currency::transfer(&who, &Self::account_id(), amount, KeepAlive);
+			let support_number = amount_number.checked_sub(fee_number).ok_or(Error::<T>::Underflow)?;
+			let pre_tax_support_pool = round.pre_tax_support_pool.checked_add(amount_number).ok_or(Error::<T>::Overflow)?;
+			let support_pool = round.support_pool.checked_add(support_number).ok_or(Error::<T>::Overflow)?;
+			let total_tax = round.total_tax.checked_add(fee_number).ok_or(Error::<T>::Overflow)?;
+			T::Currency::transfer(&who, &Self::account_id(), amount, KeepAlive)?;
 			// update the round
 			Rounds::mutate(round_id, |rnd| {
-				let ptsp = rnd.pre_tax_support_pool;
-				let sp = rnd.support_pool;
-				let tt = rnd.total_tax;
-				rnd.pre_tax_support_pool = amount_number.checked_add(ptsp).unwrap();
-				rnd.support_pool = (amount_number-fee_number).checked_add(sp).unwrap();
-				rnd.total_tax = fee_number.checked_add(tt).unwrap();
+				rnd.pre_tax_support_pool = pre_tax_support_pool;
+				rnd.support_pool = support_pool;
+				rnd.total_tax = total_tax;
 			});
-			Self::deposit_event(RawEvent::DonateSucceed(round_id, who, Self::balance_to_u128(amount)));
+			Self::deposit_event(RawEvent::DonateSucceed(round_id, who, amount_number));
 			Ok(())
 		}
 
@@ -214,6 +224,7 @@ decl_module! {
 
 		/// End an `ongoing` round and distribute the funds in sponsor pool, any invalid index or round status will cause errors
 		#[weight = 10_000 + T::DbWeight::get().reads_writes(1,1)]
+		#[transactional]
 		pub fn end_round(origin, round_id: u32) -> dispatch::DispatchResult {
 			// Only amdin can control the round 
 			T::AdminOrigin::ensure_origin(origin)?;
@@ -222,21 +233,19 @@ decl_module! {
 			ensure!(true == round.ongoing, Error::<T>::RoundHasEnded);
 			let area = round.total_support_area;
 			let pool = round.support_pool;
+			// reckon the final grants, all of them before paying any
+			let mut grants = Vec::new();
 			for (hash, mut project) in Projects::<T>::iter_prefix(round_id) {
 				if area > 0 {
-					let total = project.grants;
-					project.grants = total.checked_add(
-						project.support_area.checked_mul(pool/area).unwrap()
-					).unwrap();
+					let support_grants = project.support_area.checked_mul(pool/area).ok_or(Error::<T>::Overflow)?;
+					project.grants = project.grants.checked_add(support_grants).ok_or(Error::<T>::Overflow)?;
 				}
 				debug::info!("Hash: {:?}, Total votes: {:?}, Grants: {:?}", hash, project.total_votes, project.grants);
-				// reckon the final grants
-				let _ = T::Currency::transfer(
-					&Self::account_id(),
-					&project.owner,
-					Self::u128_to_balance(project.grants),
-					KeepAlive
-				);
+				grants.push((project.owner, Self::u128_to_balance(project.grants)?));
+			}
+			// a failed payment reverts the whole round, so no project is left half paid
+			for (owner, amount) in grants {
+				T::Currency::transfer(&Self::account_id(), &owner, amount, KeepAlive)?;
 			}
 			round.ongoing = false;
 			Rounds::insert(round_id, round);
@@ -278,27 +287,34 @@ decl_module! {
 			// need to calculate hash of project hash and round_id combination here to avoid conflicts of projects in different rounds
 			let vote_hash = T::Hashing::hash_of(&(&hash, &round_id));
 			let voted = ProjectVotes::<T>::get(vote_hash, &who);
-			let cost = Self::cal_cost(voted, ballot);
-			let amount = Self::cal_amount(cost, false);
-			let fee = Self::cal_amount(cost, true);
+			let cost = Self::cal_cost(voted, ballot)?;
+			let amount = Self::cal_amount(cost, false)?;
+			let fee = Self::cal_amount(cost, true)?;
+			let amount_balance = Self::u128_to_balance(amount)?;
+			let total_ballot = ballot.checked_add(voted).ok_or(Error::<T>::Overflow)?;
+
+			// work out the project and corresponding round before changing anything
+			let mut poj = Projects::<T>::get(round_id, hash);
+			let others_votes = poj.total_votes.checked_sub(voted).ok_or(Error::<T>::Underflow)?;
+			let support_area = ballot.checked_mul(others_votes).ok_or(Error::<T>::Overflow)?;
+			poj.support_area = support_area.checked_add(poj.support_area).ok_or(Error::<T>::Overflow)?;
+			poj.total_votes = poj.total_votes.checked_add(ballot).ok_or(Error::<T>::Overflow)?;
+			let grants = amount.checked_sub(fee).ok_or(Error::<T>::Underflow)?;
+			poj.grants = poj.grants.checked_add(grants).ok_or(Error::<T>::Overflow)?;
+			let total_support_area = support_area.checked_add(round.total_support_area).ok_or(Error::<T>::Overflow)?;
+			let total_tax = fee.checked_add(round.total_tax).ok_or(Error::<T>::Overflow)?;
+			debug::info!("Total votes: {:?}, Current votes: {:?}, Support Area: {:?},Est cost: {:?}",
+			poj.total_votes, voted, support_area, cost);
+
 			// transfer first, update last, as transfer will ensure the free balance is enough
-			let _ = T::Currency::transfer(&who, &Self::account_id(), Self::u128_to_balance(amount), KeepAlive);
+			T::Currency::transfer(&who, &Self::account_id(), amount_balance, KeepAlive)?;
 
 			// update the project and corresponding round
-			ProjectVotes::<T>::insert(vote_hash, &who, ballot+voted);
-			Projects::<T>::mutate(round_id, hash, |poj| {
-				let support_area = ballot.checked_mul(poj.total_votes - voted).unwrap();
-				poj.support_area = support_area.checked_add(poj.support_area).unwrap();
-				poj.total_votes += ballot;
-				poj.grants += amount - fee;
-				debug::info!("Total votes: {:?}, Current votes: {:?}, Support Area: {:?},Est cost: {:?}",
-				poj.total_votes, voted, support_area, cost);
-				Rounds::mutate(round_id, |rnd| {
-					let tsa = rnd.total_support_area;
-					let tt = rnd.total_tax;
-					rnd.total_support_area = support_area.checked_add(tsa).unwrap();
-					rnd.total_tax = fee.checked_add(tt).unwrap();
-				});
+			ProjectVotes::<T>::insert(vote_hash, &who, total_ballot);
+			Projects::<T>::insert(round_id, hash, poj);
+			Rounds::mutate(round_id, |rnd| {
+				rnd.total_support_area = total_support_area;
+				rnd.total_tax = total_tax;
 			});
 			Self::deposit_event(RawEvent::VoteSucceed(hash, who, ballot));
 			Ok(())
@@ -317,29 +333,31 @@ impl<T: Config> Module<T> {
 		T::ModuleId::get().into_account()
 	}
 
-	pub fn cal_cost(voted: u128, ballot: u128) -> u128 {
-		let mut points = ballot.checked_mul(ballot.checked_add(1).unwrap()).unwrap() / 2; 
-		points = points.checked_add(ballot.checked_mul(voted).unwrap()).unwrap();
-		return points;
+	pub fn cal_cost(voted: u128, ballot: u128) -> Result<u128, DispatchError> {
+		let points = ballot.checked_add(1)
+			.and_then(|next| ballot.checked_mul(next))
+			.map(|square| square / 2)
+			.and_then(|points| ballot.checked_mul(voted).and_then(|extra| points.checked_add(extra)))
+			.ok_or(Error::<T>::Overflow)?;
+		Ok(points)
 	}
 
-	pub fn cal_amount(amount: u128, is_fee: bool) -> u128 {
+	pub fn cal_amount(amount: u128, is_fee: bool) -> Result<u128, DispatchError> {
 		let uov = T::UnitOfVote::get();
 		let nup = T::NumberOfUnitPerVote::get();
 		let frpv = T::FeeRatioPerVote::get();
-		if is_fee { 
-			uov.checked_mul(frpv).unwrap().checked_mul(amount).unwrap() 
-		} else {
-			uov.checked_mul(nup).unwrap().checked_mul(amount).unwrap()
-		}
+		let ratio = if is_fee { frpv } else { nup };
+		uov.checked_mul(ratio)
+			.and_then(|unit| unit.checked_mul(amount))
+			.ok_or(Error::<T>::Overflow.into())
 	}
 
-	pub fn u128_to_balance(cost: u128) -> BalanceOf<T> {
-		TryInto::<BalanceOf::<T>>::try_into(cost).ok().unwrap()
+	pub fn u128_to_balance(cost: u128) -> Result<BalanceOf<T>, DispatchError> {
+		TryInto::<BalanceOf::<T>>::try_into(cost).map_err(|_| Error::<T>::BalanceConversion.into())
 	}
 
-	pub fn balance_to_u128(balance: BalanceOf<T>) -> u128 {
-		TryInto::<u128>::try_into(balance).ok().unwrap()
+	pub fn balance_to_u128(balance: BalanceOf<T>) -> Result<u128, DispatchError> {
+		TryInto::<u128>::try_into(balance).map_err(|_| Error::<T>::BalanceConversion.into())
 	}
 
 	// TODO: There is a bug for serde_json, can not use u128 https://github.com/paritytech/substrate/issues/4641
@@ -347,7 +365,8 @@ impl<T: Config> Module<T> {
 		// need to calculate hash of project hash and round_id combination here to avoid conflicts of projects in different rounds
 		let vote_hash = T::Hashing::hash_of(&(&hash, &round_id));
 		let voted = ProjectVotes::<T>::get(vote_hash, &who);
-		TryInto::<u32>::try_into(Self::cal_cost(voted, ballot.into())).ok().unwrap()
+		// the runtime api can not report errors, a cost out of range saturates
+		Self::cal_cost(voted, ballot.into()).map_or(u32::max_value(), |cost| cost.saturated_into::<u32>())
 	}
 
 	// TODO, using struct is a little complicate, use tuple instead
@@ -360,11 +379,12 @@ impl<T: Config> Module<T> {
 		for (hash, project) in Projects::<T>::iter_prefix(round_id) {
 			let mut sg = 0;
 			if area > 0 {
-				sg = project.support_area.checked_mul(pool/area).unwrap()
+				sg = project.support_area.saturating_mul(pool/area)
 			}
-			let total_votes = TryInto::<u32>::try_into(project.total_votes).ok().unwrap();
-			let grants = TryInto::<u32>::try_into(project.grants.checked_div(T::UnitOfVote::get()).unwrap()).ok().unwrap();
-			let support_grants = TryInto::<u32>::try_into(sg.checked_div(T::UnitOfVote::get()).unwrap()).ok().unwrap();
+			// same as `vote_cost`, the numbers saturate
+			let total_votes = project.total_votes.saturated_into::<u32>();
+			let grants = project.grants.checked_div(T::UnitOfVote::get()).unwrap_or_default().saturated_into::<u32>();
+			let support_grants = sg.checked_div(T::UnitOfVote::get()).unwrap_or_default().saturated_into::<u32>();
 			projects.push((hash, total_votes, grants, support_grants))
 		}
 		projects
@@ -1,7 +1,7 @@
 use crate::{Module, Config};
 use frame_system as system;
 use sp_core::H256;
-use frame_support::{impl_outer_origin, impl_outer_event, parameter_types, weights::Weight};
+use frame_support::{impl_outer_origin, impl_outer_event, parameter_types, weights::Weight, storage::unhashed, traits::Get};
 use sp_runtime::{
 	Perbill, ModuleId,
 	testing::Header,
@@ -37,8 +37,6 @@ parameter_types! {
 parameter_types! {
 	// for testing, set unit to pico
     pub const VoteUnit: u128 = 1;
-	// The base of unit per vote, should be 100 pico of token for each vote
-    pub const NumberOfUnit: u128 = 100;
     // The ratio of fee for each trans, final value should be FeeRatio/NumberOfUnit
     pub const FeeRatio: u128 = 5;
 	pub const QuadraticFundingModuleId: ModuleId = ModuleId(*b"py/quafd");
@@ -116,17 +114,35 @@ impl Config for Test {
 	type NameMaxLength = NameMaxLength;
 }
 
+/// The base of unit per vote, should be 100 pico of token for each vote, tests can change it
+pub struct NumberOfUnit;
+
+impl NumberOfUnit {
+	pub fn set(number: u128) {
+		unhashed::put(b"mock_number_of_unit", &number);
+	}
+}
+
+impl Get<u128> for NumberOfUnit {
+	fn get() -> u128 {
+		unhashed::get_or(b"mock_number_of_unit", 100)
+	}
+}
+
 pub type System = frame_system::Module<Test>;
 pub type Balances = pallet_balances::Module<Test>;
 pub type QuadraticFunding = Module<Test>;
 
+/// Account too poor to pay for anything in the tests
+pub const POOR: u64 = 4;
+
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	// system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
 	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
 	pallet_balances::GenesisConfig::<Test>{
 		// Total issuance will be 1000 with internal account initialized at ED.
-		balances: vec![(0, 1000), (1, 2000), (2, 3000), (3, 4000)],
+		balances: vec![(0, 1000), (1, 2000), (2, 3000), (3, 4000), (POOR, 10)],
 	}.assimilate_storage(&mut t).unwrap();
 	system::GenesisConfig::default().assimilate_storage::<Test>(&mut t).unwrap();
 	t.into()
@@ -1,7 +1,8 @@
 use crate::{Error, mock::*};
-use frame_support::{assert_ok, assert_noop};
+use frame_support::{assert_ok, assert_noop, traits::Currency};
 use sp_core::H256;
 use sp_runtime::traits::BadOrigin;
+use pallet_balances::{Error as BalancesError, DefaultInstance};
 use super::RawEvent;
 
 /// generate a Hash for indexing project
@@ -135,4 +136,58 @@ fn vote_with_fund_works() {
 		assert_eq!(QuadraticFunding::rounds(round_id).total_support_area, 18);
 		
 	});
-}
\ No newline at end of file
+}
+
+#[test]
+fn arithmetic_edges_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let round_id = 1;
+		let hash = get_hash(1);
+		assert_ok!(QuadraticFunding::start_round(Origin::root(), round_id));
+		assert_ok!(QuadraticFunding::register_project(Origin::signed(1), round_id, hash, b"name".to_vec()));
+
+		// the cost of the ballot does not fit in u128
+		assert_noop!(QuadraticFunding::vote(Origin::signed(1), round_id, hash, u128::max_value()), Error::<Test>::Overflow);
+		assert_eq!(QuadraticFunding::cal_cost(u128::max_value(), 2), Err(Error::<Test>::Overflow.into()));
+		// the cost fits in u128, but not in the balance of the mock
+		assert_noop!(QuadraticFunding::vote(Origin::signed(1), round_id, hash, 1 << 40), Error::<Test>::BalanceConversion);
+		assert_eq!(QuadraticFunding::u128_to_balance(u128::max_value()), Err(Error::<Test>::BalanceConversion.into()));
+		assert_eq!(QuadraticFunding::projects(round_id, hash).total_votes, 0);
+
+		// the runtime api saturates instead
+		assert_eq!(QuadraticFunding::vote_cost(1, round_id, hash, u32::max_value()), u32::max_value());
+
+		// a misconfigured number of units per vote can not be divided by
+		NumberOfUnit::set(0);
+		assert_noop!(QuadraticFunding::donate(Origin::signed(1), round_id, 500), Error::<Test>::DivisionByZero);
+	});
+}
+
+#[test]
+fn underfunded_transfers_change_nothing() {
+	new_test_ext().execute_with(|| {
+		let round_id = 1;
+		let hash = get_hash(1);
+		assert_ok!(QuadraticFunding::start_round(Origin::root(), round_id));
+		assert_ok!(QuadraticFunding::register_project(Origin::signed(1), round_id, hash, b"name".to_vec()));
+
+		// neither the donation nor the ballot is recorded without the payment
+		assert_noop!(
+			QuadraticFunding::donate(Origin::signed(POOR), round_id, 500),
+			BalancesError::<Test, DefaultInstance>::InsufficientBalance
+		);
+		assert_noop!(
+			QuadraticFunding::vote(Origin::signed(POOR), round_id, hash, 1),
+			BalancesError::<Test, DefaultInstance>::InsufficientBalance
+		);
+
+		// the grants can not be paid, so the round stays open
+		assert_ok!(QuadraticFunding::vote(Origin::signed(2), round_id, hash, 3));
+		let _ = Balances::make_free_balance_be(&QuadraticFunding::account_id(), 1);
+		assert_noop!(
+			QuadraticFunding::end_round(Origin::root(), round_id),
+			BalancesError::<Test, DefaultInstance>::InsufficientBalance
+		);
+		assert!(QuadraticFunding::rounds(round_id).ongoing);
+	});
+}
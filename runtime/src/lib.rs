@@ -10,7 +10,7 @@ use sp_std::prelude::*;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	ApplyExtrinsicResult, generic, create_runtime_str, impl_opaque_keys, MultiSignature,
-	transaction_validity::{TransactionValidity, TransactionSource}, ModuleId,
+	transaction_validity::{TransactionValidity, TransactionSource, TransactionPriority}, ModuleId,
 };
 use sp_runtime::traits::{
 	BlakeTwo256, Block as BlockT, Verify, IdentifyAccount, NumberFor, Saturating,
@@ -41,6 +41,7 @@ pub use frame_support::{
 
 /// Import the template pallet.
 pub use pallet_quadratic_funding;
+pub use pallet_moloch_v1;
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -96,10 +97,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("node-template"),
 	impl_name: create_runtime_str!("node-template"),
 	authoring_version: 1,
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 pub const MILLISECS_PER_BLOCK: u64 = 6000;
@@ -317,6 +318,13 @@ parameter_types! {
     pub const MaxGracePeriodLength: u128 = 10_u128.pow(18); // maximum length of grace period
     pub const MaxDilutionBound: u128 = 10_u128.pow(18); // maximum dilution bound
     pub const MaxShares: u128 = 10_u128.pow(18); // maximum number of shares that can be minted
+    pub const MaxWhitelistedTokens: u32 = 100; // maximum number of tokens a guild can accept as tribute
+	pub MaxActionWeight: Weight = Perbill::from_percent(50) * MaximumBlockWeight::get();
+	pub const MaxDelegators: u32 = 64; // maximum number of members delegating their vote to the same member
+	pub const MaxDetailsLength: u32 = 1024; // maximum length of the details of a proposal, longer bodies go off-chain
+	// Room for processing one proposal with the heaviest action at the start of a block
+	pub AutoProcessWeight: Weight = Perbill::from_percent(60) * MaximumBlockWeight::get();
+	pub const UnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
 
 }
 
@@ -336,6 +344,9 @@ impl pallet_moloch_v1::Config for Runtime {
 	// Origin who can control the round
 	type AdminOrigin = EnsureRoot<AccountId>;
 
+	// Periods are counted in seconds
+	type PeriodClock = pallet_moloch_v1::TimestampClock<Runtime>;
+
 	// maximum length of voting period
 	type MaxVotingPeriodLength = MaxVotingPeriodLength;
 
@@ -348,6 +359,36 @@ impl pallet_moloch_v1::Config for Runtime {
 	// maximum number of shares
 	type MaxShares = MaxShares;
 
+	// No assets pallet yet, tribute is paid in the native currency only
+	type Assets = ();
+
+	// maximum number of whitelisted tokens of a guild
+	type MaxWhitelistedTokens = MaxWhitelistedTokens;
+
+	type Origin = Origin;
+
+	// Action proposals can dispatch any call of the runtime
+	type Action = Call;
+
+	// maximum weight of the call of an action proposal
+	type MaxActionWeight = MaxActionWeight;
+
+	// maximum number of members delegating their vote to the same member
+	type MaxDelegators = MaxDelegators;
+
+	// maximum length of the details of a proposal and of the uri of its content
+	type MaxDetailsLength = MaxDetailsLength;
+
+	// weight on_initialize may spend processing ready proposals
+	type AutoProcessWeight = AutoProcessWeight;
+
+	// priority of the proposals processed by the off-chain worker
+	type UnsignedPriority = UnsignedPriority;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime where Call: From<C> {
+	type OverarchingCall = Call;
+	type Extrinsic = UncheckedExtrinsic;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -367,7 +408,7 @@ construct_runtime!(
 		Sudo: pallet_sudo::{Module, Call, Config<T>, Storage, Event<T>},
 		// Include the custom logic from the template pallet in the runtime.
 		QuadraticFunding: pallet_quadratic_funding::{Module, Call, Storage, Event<T>},
-		MolochV1: pallet_moloch_v1::{Module, Call, Storage, Event<T>},
+		MolochV1: pallet_moloch_v1::{Module, Call, Storage, Event<T>, Config<T>, Origin, ValidateUnsigned},
 	}
 );
 
@@ -541,6 +582,27 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_moloch_v1_runtime_api::MolochApi<Block, AccountId, Balance, Hash> for Runtime {
+		fn current_period(dao_id: pallet_moloch_v1::DaoId) -> Option<u128> {
+			MolochV1::current_period(dao_id)
+		}
+		fn member_info(dao_id: pallet_moloch_v1::DaoId, who: AccountId) -> Option<(u128, u128, Option<u128>, AccountId)> {
+			MolochV1::member_info(dao_id, who)
+		}
+		fn proposal_status(dao_id: pallet_moloch_v1::DaoId, proposal_index: u128) -> Option<pallet_moloch_v1::ProposalStatus> {
+			MolochV1::proposal_status(dao_id, proposal_index)
+		}
+		fn proposal_content(dao_id: pallet_moloch_v1::DaoId, proposal_index: u128) -> Option<pallet_moloch_v1::ProposalContent<Hash>> {
+			MolochV1::proposal_content(dao_id, proposal_index)
+		}
+		fn ragequit_preview(dao_id: pallet_moloch_v1::DaoId, shares_to_burn: u128) -> Balance {
+			MolochV1::ragequit_preview(dao_id, shares_to_burn)
+		}
+		fn guild_bank_balance(dao_id: pallet_moloch_v1::DaoId) -> Balance {
+			MolochV1::guild_bank_balance(dao_id)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn dispatch_benchmark(